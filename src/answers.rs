@@ -0,0 +1,215 @@
+use std::{
+    collections::BTreeMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{puzzles::Answer, runner::DayReport, Error, Result, YEAR};
+
+#[derive(Serialize, Deserialize, Default, Clone, PartialEq, Eq, Debug)]
+struct Parts {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part2: Option<String>,
+}
+
+/// Accepted answers per day and part, stored as `answers/2024.toml`.
+pub struct AnswerStore {
+    path: PathBuf,
+    days: BTreeMap<i32, Parts>,
+}
+
+impl AnswerStore {
+    pub fn default_path() -> PathBuf {
+        Path::new("answers").join(format!("{}.toml", YEAR))
+    }
+
+    /// Loads the store at `path`, an absent file is an empty store.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err.into()),
+        };
+
+        let invalid = |message: String| Error::InvalidAnswers(path.clone(), message);
+        let days = toml::from_str::<BTreeMap<String, Parts>>(&content)
+            .map_err(|err| invalid(err.message().to_string()))?
+            .into_iter()
+            .map(|(key, parts)| {
+                key.strip_prefix("day")
+                    .and_then(|day| day.parse().ok())
+                    .map(|day| (day, parts))
+                    .ok_or_else(|| invalid(format!("`{}` is not a day like `day07`", key)))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { path, days })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let days: BTreeMap<String, &Parts> = self
+            .days
+            .iter()
+            .map(|(day, parts)| (format!("day{:02}", day), parts))
+            .collect();
+        let content = toml::to_string(&days)
+            .map_err(|err| Error::InvalidAnswers(self.path.clone(), err.to_string()))?;
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, content)?;
+
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn get(&self, day: i32, part: u8) -> Option<&str> {
+        let parts = self.days.get(&day)?;
+        match part {
+            1 => parts.part1.as_deref(),
+            2 => parts.part2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn record(&mut self, day: i32, part: u8, answer: impl Into<String>) {
+        let parts = self.days.entry(day).or_default();
+        match part {
+            1 => parts.part1 = Some(answer.into()),
+            2 => parts.part2 = Some(answer.into()),
+            _ => {}
+        }
+    }
+
+    /// Compares both parts of `report` with the recorded answers.
+    pub fn verify(&self, report: &DayReport) -> Vec<Verdict> {
+        report
+            .parts
+            .iter()
+            .zip(1..)
+            .map(|(part, idx)| {
+                let actual = match &part.answer {
+                    Ok(Answer::Unsolved) => None,
+                    Ok(answer) => Some(answer.to_string()),
+                    Err(err) => return Verdict::Failed(err.to_string()),
+                };
+
+                match (self.get(report.day, idx), actual) {
+                    (None, actual) => Verdict::Unrecorded(actual),
+                    (Some(expected), Some(actual)) if expected == actual => Verdict::Pass(actual),
+                    (Some(expected), actual) => Verdict::Mismatch {
+                        expected: expected.to_string(),
+                        actual,
+                    },
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Verdict {
+    Pass(String),
+    Mismatch {
+        expected: String,
+        actual: Option<String>,
+    },
+    /// No answer is recorded yet, holds what the puzzle computed if it is solved.
+    Unrecorded(Option<String>),
+    Failed(String),
+}
+
+impl Verdict {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Verdict::Mismatch { .. } | Verdict::Failed(_))
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass(answer) => write!(f, "ok ({})", answer),
+            Verdict::Mismatch { expected, actual } => write!(
+                f,
+                "MISMATCH expected {}, got {}",
+                expected,
+                actual.as_deref().unwrap_or("unsolved")
+            ),
+            Verdict::Unrecorded(Some(answer)) => write!(f, "no recorded answer ({})", answer),
+            Verdict::Unrecorded(None) => write!(f, "unsolved"),
+            Verdict::Failed(message) => write!(f, "FAILED {}", message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cache::temp_dir, puzzles, runner::run_day};
+
+    const TESTCASE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+    fn temp_path(name: &str) -> PathBuf {
+        temp_dir(name).join("answers.toml")
+    }
+
+    #[test]
+    fn test_answer_store_roundtrip() {
+        let path = temp_path("answers");
+
+        let mut store = AnswerStore::load(&path).unwrap();
+        assert_eq!(store.get(1, 1), None);
+
+        store.record(1, 1, "11");
+        store.record(10, 2, "81");
+        store.record(2, 1, "2");
+        store.save().unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.find("[day02]").unwrap() < content.find("[day10]").unwrap());
+
+        let store = AnswerStore::load(&path).unwrap();
+        assert_eq!(store.get(1, 1), Some("11"));
+        assert_eq!(store.get(1, 2), None);
+        assert_eq!(store.get(10, 2), Some("81"));
+
+        fs::write(&path, "[tuesday]\npart1 = \"1\"\n").unwrap();
+        assert!(matches!(
+            AnswerStore::load(&path),
+            Err(Error::InvalidAnswers(_, _))
+        ));
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_answer_store_verify() {
+        let mut store = AnswerStore::load(temp_path("verify")).unwrap();
+        let report = run_day(puzzles::get(1).unwrap(), TESTCASE);
+
+        assert_eq!(
+            store.verify(&report),
+            vec![
+                Verdict::Unrecorded(Some(String::from("11"))),
+                Verdict::Unrecorded(Some(String::from("31"))),
+            ]
+        );
+
+        store.record(1, 1, "11");
+        store.record(1, 2, "32");
+        let verdicts = store.verify(&report);
+
+        assert_eq!(verdicts[0], Verdict::Pass(String::from("11")));
+        assert!(verdicts[1].is_failure());
+        assert_eq!(verdicts[1].to_string(), "MISMATCH expected 32, got 31");
+    }
+}
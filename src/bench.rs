@@ -0,0 +1,250 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{puzzles::Puzzle, runner::Elapsed, Error, Result};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BenchOptions {
+    pub warmup: usize,
+    pub iterations: usize,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            warmup: 3,
+            iterations: 10,
+        }
+    }
+}
+
+/// Summary of the samples of one part, in nanoseconds.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub struct Stats {
+    pub mean_ns: f64,
+    pub median_ns: f64,
+    pub stddev_ns: f64,
+    pub samples: usize,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut nanos: Vec<f64> = samples.iter().map(|s| s.as_nanos() as f64).collect();
+        nanos.sort_by(f64::total_cmp);
+
+        let count = nanos.len().max(1) as f64;
+        let mean = nanos.iter().sum::<f64>() / count;
+        let variance = nanos.iter().map(|ns| (ns - mean).powi(2)).sum::<f64>() / count;
+        let median = match nanos.len() {
+            0 => 0.0,
+            len if len % 2 == 0 => (nanos[len / 2 - 1] + nanos[len / 2]) / 2.0,
+            len => nanos[len / 2],
+        };
+
+        Self {
+            mean_ns: mean,
+            median_ns: median,
+            stddev_ns: variance.sqrt(),
+            samples: nanos.len(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct BenchResult {
+    pub day: i32,
+    pub part: u8,
+    pub stats: Stats,
+}
+
+/// Times `part` of `puzzle` on `input`, the warmup runs are not measured.
+pub fn bench_part(
+    puzzle: &dyn Puzzle,
+    part: u8,
+    input: &str,
+    options: BenchOptions,
+) -> Result<BenchResult> {
+    for _ in 0..options.warmup {
+        puzzle.part(part, input)?;
+    }
+
+    let samples = (0..options.iterations.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            puzzle.part(part, input).map(|_| start.elapsed())
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(BenchResult {
+        day: puzzle.day(),
+        part,
+        stats: Stats::from_samples(&samples),
+    })
+}
+
+/// Named set of results saved as `target/bench/<name>.json`.
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct Baseline {
+    pub results: Vec<BenchResult>,
+}
+
+impl Baseline {
+    pub fn path(name: &str) -> PathBuf {
+        Path::new("target")
+            .join("bench")
+            .join(format!("{}.json", name))
+    }
+
+    pub fn load(name: &str) -> Result<Self> {
+        let path = Self::path(name);
+        let content = fs::read_to_string(&path).map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => Error::MissingBaseline(path.clone()),
+            _ => err.into(),
+        })?;
+
+        serde_json::from_str(&content).map_err(|err| Error::InvalidBaseline(path, err.to_string()))
+    }
+
+    pub fn save(&self, name: &str) -> Result<()> {
+        let path = Self::path(name);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let content = serde_json::to_string_pretty(self).expect("results always serialize");
+        fs::write(path, content)?;
+
+        Ok(())
+    }
+
+    pub fn get(&self, day: i32, part: u8) -> Option<&Stats> {
+        self.results
+            .iter()
+            .find(|result| result.day == day && result.part == part)
+            .map(|result| &result.stats)
+    }
+}
+
+fn elapsed(nanos: f64) -> Elapsed {
+    Elapsed(Duration::from_nanos(nanos.round() as u64))
+}
+
+/// Relative change of the mean against `baseline`, e.g. `+12.5%`.
+fn change(stats: &Stats, baseline: Option<&Stats>) -> String {
+    match baseline {
+        Some(baseline) if baseline.mean_ns > 0.0 => {
+            format!("{:+.1}%", (stats.mean_ns / baseline.mean_ns - 1.0) * 100.0)
+        }
+        _ => String::from("-"),
+    }
+}
+
+/// Renders `results` as a table, with a change column when comparing to `baseline`.
+pub fn render(results: &[BenchResult], baseline: Option<&Baseline>) -> String {
+    let mut lines = vec![format!(
+        "{:>3} | {:>4} | {:>8} | {:>8} | {:>8}{}",
+        "Day",
+        "Part",
+        "Mean",
+        "Median",
+        "Stddev",
+        if baseline.is_some() { " | Change" } else { "" }
+    )];
+
+    for result in results {
+        let mut line = format!(
+            "{:>3} | {:>4} | {:>8} | {:>8} | {:>8}",
+            result.day,
+            result.part,
+            elapsed(result.stats.mean_ns),
+            elapsed(result.stats.median_ns),
+            elapsed(result.stats.stddev_ns)
+        );
+
+        if let Some(baseline) = baseline {
+            let previous = baseline.get(result.day, result.part);
+            line.push_str(&format!(" | {:>6}", change(&result.stats, previous)));
+        }
+
+        lines.push(line);
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzles;
+
+    #[test]
+    fn test_stats_from_samples() {
+        let samples = [4, 2, 8, 6].map(Duration::from_nanos);
+        let stats = Stats::from_samples(&samples);
+
+        assert_eq!(stats.mean_ns, 5.0);
+        assert_eq!(stats.median_ns, 5.0);
+        assert_eq!(stats.stddev_ns, 5.0_f64.sqrt());
+        assert_eq!(stats.samples, 4);
+
+        let stats = Stats::from_samples(&[3, 1, 2].map(Duration::from_nanos));
+        assert_eq!(stats.median_ns, 2.0);
+    }
+
+    #[test]
+    fn test_bench_part() {
+        let puzzle = puzzles::get(1).unwrap();
+        let options = BenchOptions {
+            warmup: 1,
+            iterations: 5,
+        };
+
+        let result = bench_part(puzzle, 2, "3   4\n4   3\n", options).unwrap();
+        assert_eq!((result.day, result.part), (1, 2));
+        assert_eq!(result.stats.samples, 5);
+
+        assert!(bench_part(puzzle, 1, "3   4\n4\n", options).is_err());
+    }
+
+    #[test]
+    fn test_render_with_baseline() {
+        let stats = |mean_ns| Stats {
+            mean_ns,
+            median_ns: mean_ns,
+            stddev_ns: 0.0,
+            samples: 1,
+        };
+        let baseline = Baseline {
+            results: vec![BenchResult {
+                day: 1,
+                part: 1,
+                stats: stats(1000.0),
+            }],
+        };
+        let results = [
+            BenchResult {
+                day: 1,
+                part: 1,
+                stats: stats(1250.0),
+            },
+            BenchResult {
+                day: 1,
+                part: 2,
+                stats: stats(500.0),
+            },
+        ];
+
+        let table = render(&results, Some(&baseline));
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert!(lines[0].ends_with("| Change"));
+        assert!(lines[1].ends_with("+25.0%"));
+        assert!(lines[2].ends_with("-"));
+        assert!(!render(&results, None).contains("Change"));
+    }
+}
@@ -0,0 +1,91 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::YEAR;
+
+/// On-disk store of puzzle inputs, laid out as `<root>/2024/dayNN.txt`.
+pub struct InputCache {
+    root: PathBuf,
+}
+
+impl InputCache {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn path(&self, day: i32) -> PathBuf {
+        self.root
+            .join(YEAR.to_string())
+            .join(format!("day{:02}.txt", day))
+    }
+
+    pub fn load(&self, day: i32) -> io::Result<Option<String>> {
+        match fs::read_to_string(self.path(day)) {
+            Ok(input) => Ok(Some(input)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn store(&self, day: i32, input: &str) -> io::Result<()> {
+        let path = self.path(day);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::write(path, input)
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl Default for InputCache {
+    fn default() -> Self {
+        Self::new("inputs")
+    }
+}
+
+/// Empty directory for the files of test `name`, unique to this process.
+#[cfg(test)]
+pub(crate) fn temp_dir(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("aoc2024-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+
+    root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_cache(name: &str) -> InputCache {
+        InputCache::new(temp_dir(name))
+    }
+
+    #[test]
+    fn test_input_cache_path() {
+        let cache = InputCache::new("inputs");
+
+        assert_eq!(cache.path(7), Path::new("inputs/2024/day07.txt"));
+        assert_eq!(cache.path(17), Path::new("inputs/2024/day17.txt"));
+    }
+
+    #[test]
+    fn test_input_cache_roundtrip() {
+        let cache = temp_cache("roundtrip");
+
+        assert_eq!(cache.load(1).unwrap(), None);
+
+        cache.store(1, "3   4\n4   3\n").unwrap();
+        assert_eq!(cache.load(1).unwrap(), Some(String::from("3   4\n4   3\n")));
+
+        cache.store(1, "1   2\n").unwrap();
+        assert_eq!(cache.load(1).unwrap(), Some(String::from("1   2\n")));
+
+        fs::remove_dir_all(cache.root()).unwrap();
+    }
+}
@@ -0,0 +1,358 @@
+use std::{fmt, fs, io, path::Path, time::Duration};
+
+use reqwest::{
+    header::{HeaderMap, HeaderValue},
+    Client,
+};
+
+use crate::{Error, Result, YEAR};
+
+const BASE_URL: &str = "https://adventofcode.com";
+
+/// Cookie-authenticated client for the Advent of Code website.
+pub struct AocClient {
+    base_url: String,
+    client: Client,
+}
+
+impl AocClient {
+    pub fn new(cookie: &str) -> Result<Self> {
+        Self::with_base_url(cookie, BASE_URL)
+    }
+
+    pub fn with_base_url(cookie: &str, base_url: &str) -> Result<Self> {
+        let mut headers = HeaderMap::new();
+        let cookie = HeaderValue::from_str(cookie.trim())
+            .map_err(|err| Error::Fetch(format!("invalid session cookie: {}", err)))?;
+        headers.insert("COOKIE", cookie);
+        let client = Client::builder().default_headers(headers).build()?;
+
+        Ok(Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client,
+        })
+    }
+
+    pub fn from_cookie_file(path: impl AsRef<Path>) -> Result<Self> {
+        let cookie = fs::read_to_string(&path).map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => Error::MissingCookie(path.as_ref().to_path_buf()),
+            _ => Error::Io(err),
+        })?;
+
+        Self::new(&cookie)
+    }
+
+    pub async fn fetch_input(&self, day: i32) -> Result<String> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, YEAR, day);
+        let resp = self.client.get(url).send().await?;
+
+        let status = resp.status();
+        let text = resp.text().await?;
+        if !status.is_success() {
+            return Err(Error::Fetch(format!(
+                "error getting puzzle input ({}): {}",
+                status,
+                text.trim()
+            )));
+        }
+
+        Ok(text)
+    }
+
+    pub async fn submit(&self, day: i32, part: u8, answer: &str) -> Result<SubmitOutcome> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, YEAR, day);
+        let level = part.to_string();
+        let resp = self
+            .client
+            .post(url)
+            .form(&[("level", level.as_str()), ("answer", answer)])
+            .send()
+            .await?;
+
+        let status = resp.status();
+        let text = resp.text().await?;
+        if !status.is_success() {
+            return Err(Error::Fetch(format!(
+                "error submitting answer ({}): {}",
+                status,
+                text.trim()
+            )));
+        }
+
+        Ok(SubmitOutcome::parse(&text))
+    }
+}
+
+/// What the server said about a submitted answer.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum SubmitOutcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    RateLimited { wait: Option<Duration> },
+    AlreadySolved,
+    Unrecognized(String),
+}
+
+impl SubmitOutcome {
+    pub fn parse(html: &str) -> Self {
+        let message = article_text(html);
+
+        if message.contains("That's the right answer") {
+            SubmitOutcome::Correct
+        } else if message.contains("You gave an answer too recently") {
+            SubmitOutcome::RateLimited {
+                wait: parse_wait(&message),
+            }
+        } else if message.contains("You don't seem to be solving the right level") {
+            SubmitOutcome::AlreadySolved
+        } else if message.contains("That's not the right answer") {
+            if message.contains("your answer is too high") {
+                SubmitOutcome::TooHigh
+            } else if message.contains("your answer is too low") {
+                SubmitOutcome::TooLow
+            } else {
+                SubmitOutcome::Wrong
+            }
+        } else {
+            SubmitOutcome::Unrecognized(message)
+        }
+    }
+}
+
+impl fmt::Display for SubmitOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitOutcome::Correct => write!(f, "That's the right answer!"),
+            SubmitOutcome::Wrong => write!(f, "That's not the right answer."),
+            SubmitOutcome::TooHigh => write!(f, "That's not the right answer, too high."),
+            SubmitOutcome::TooLow => write!(f, "That's not the right answer, too low."),
+            SubmitOutcome::RateLimited { wait: Some(wait) } => {
+                write!(f, "Answered too recently, wait {}s.", wait.as_secs())
+            }
+            SubmitOutcome::RateLimited { wait: None } => write!(f, "Answered too recently."),
+            SubmitOutcome::AlreadySolved => write!(f, "This part is already solved."),
+            SubmitOutcome::Unrecognized(message) => write!(f, "Unrecognized response: {}", message),
+        }
+    }
+}
+
+/// Text of the `<article>` holding the server's message, with tags removed.
+fn article_text(html: &str) -> String {
+    let body = match (html.find("<article"), html.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for ch in body.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(ch),
+            _ => (),
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parses the `You have 1m 5s left to wait` part of a rate limit message.
+fn parse_wait(message: &str) -> Option<Duration> {
+    let (before, _) = message.split_once(" left to wait")?;
+    let (_, amount) = before.rsplit_once("You have ")?;
+
+    amount
+        .split_whitespace()
+        .map(|part| {
+            let (value, unit) = part.split_at(part.find(|ch: char| !ch.is_ascii_digit())?);
+            let value: u64 = value.parse().ok()?;
+
+            match unit {
+                "h" => Some(value * 3600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    use super::*;
+
+    fn response_page(message: &str) -> String {
+        format!(
+            "<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>",
+            message
+        )
+    }
+
+    /// Serves a single HTTP request with `body`, returning the raw request it received.
+    fn stand_in_server(status: &'static str, body: String) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+
+            loop {
+                let n = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+
+                let text = String::from_utf8_lossy(&request);
+                if let Some(header_end) = text.find("\r\n\r\n") {
+                    let content_length = text[..header_end]
+                        .lines()
+                        .find_map(|line| {
+                            let (name, value) = line.split_once(':')?;
+                            name.eq_ignore_ascii_case("content-length")
+                                .then(|| value.trim().parse::<usize>().ok())?
+                        })
+                        .unwrap_or(0);
+
+                    if request.len() >= header_end + 4 + content_length {
+                        break;
+                    }
+                }
+            }
+
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+
+            String::from_utf8(request).unwrap()
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn test_submit_outcome_parse() {
+        assert_eq!(
+            SubmitOutcome::parse(&response_page(
+                "That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer."
+            )),
+            SubmitOutcome::Correct
+        );
+        assert_eq!(
+            SubmitOutcome::parse(&response_page(
+                "That's not the right answer.  If you're stuck, make sure you're using the full input data."
+            )),
+            SubmitOutcome::Wrong
+        );
+        assert_eq!(
+            SubmitOutcome::parse(&response_page(
+                "That's not the right answer; your answer is too high.  Please wait one minute before trying again."
+            )),
+            SubmitOutcome::TooHigh
+        );
+        assert_eq!(
+            SubmitOutcome::parse(&response_page(
+                "That's not the right answer; your answer is too low."
+            )),
+            SubmitOutcome::TooLow
+        );
+        assert_eq!(
+            SubmitOutcome::parse(&response_page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            SubmitOutcome::AlreadySolved
+        );
+        assert_eq!(
+            SubmitOutcome::parse("<html>Something else</html>"),
+            SubmitOutcome::Unrecognized(String::from("Something else"))
+        );
+    }
+
+    #[test]
+    fn test_submit_outcome_parse_rate_limited() {
+        assert_eq!(
+            SubmitOutcome::parse(&response_page(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 38s left to wait."
+            )),
+            SubmitOutcome::RateLimited {
+                wait: Some(Duration::from_secs(38))
+            }
+        );
+        assert_eq!(
+            SubmitOutcome::parse(&response_page(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 51s left to wait."
+            )),
+            SubmitOutcome::RateLimited {
+                wait: Some(Duration::from_secs(291))
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_client_fetch_input() {
+        let (base_url, server) = stand_in_server("200 OK", String::from("125 17\n"));
+        let client = AocClient::with_base_url("session=abc", &base_url).unwrap();
+
+        assert_eq!(client.fetch_input(11).await.unwrap(), "125 17\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2024/day/11/input "));
+        assert!(request.to_lowercase().contains("cookie: session=abc"));
+    }
+
+    #[tokio::test]
+    async fn test_client_fetch_input_error_status() {
+        let (base_url, server) = stand_in_server(
+            "400 Bad Request",
+            String::from("Puzzle inputs differ by user.  Please log in to get your puzzle input."),
+        );
+        let client = AocClient::with_base_url("session=abc", &base_url).unwrap();
+
+        let err = client.fetch_input(1).await.unwrap_err();
+        assert!(err.to_string().contains("Please log in"));
+
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_client_missing_cookie() {
+        match AocClient::from_cookie_file("does-not-exist/.cookie") {
+            Err(Error::MissingCookie(path)) => {
+                assert_eq!(path, Path::new("does-not-exist/.cookie"))
+            }
+            _ => panic!("expected a missing cookie error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_client_submit() {
+        let (base_url, server) = stand_in_server(
+            "200 OK",
+            response_page("That's not the right answer; your answer is too low."),
+        );
+        let client = AocClient::with_base_url("session=abc", &base_url).unwrap();
+
+        assert_eq!(
+            client.submit(7, 2, "11387").await.unwrap(),
+            SubmitOutcome::TooLow
+        );
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2024/day/7/answer "));
+        assert!(request.ends_with("level=2&answer=11387"));
+    }
+}
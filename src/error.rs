@@ -0,0 +1,175 @@
+use std::{fmt, io, path::PathBuf};
+
+#[derive(Debug)]
+pub enum Error {
+    Parse(ParseError),
+    Fetch(String),
+    MissingCookie(PathBuf),
+    NotCached(PathBuf),
+    UnsupportedDay(i32),
+    UnsupportedPart(u8),
+    Solve(String),
+    InvalidAnswers(PathBuf, String),
+    Mismatch(usize),
+    Failed(usize),
+    MissingBaseline(PathBuf),
+    InvalidBaseline(PathBuf, String),
+    Io(io::Error),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(err) => write!(f, "{}", err),
+            Error::Fetch(message) => write!(f, "failed to talk to adventofcode.com: {}", message),
+            Error::MissingCookie(path) => {
+                write!(f, "session cookie not found, put it in {}", path.display())
+            }
+            Error::NotCached(path) => write!(
+                f,
+                "input is not cached at {} and --offline is set",
+                path.display()
+            ),
+            Error::UnsupportedDay(day) => write!(f, "puzzle of day {} is not implemented", day),
+            Error::UnsupportedPart(part) => {
+                write!(f, "part {} does not exist, only 1 and 2 do", part)
+            }
+            Error::Solve(message) => write!(f, "failed to solve puzzle: {}", message),
+            Error::InvalidAnswers(path, message) => {
+                write!(f, "invalid answers file {}: {}", path.display(), message)
+            }
+            Error::Mismatch(count) => {
+                write!(f, "{} answer(s) do not match the recorded ones", count)
+            }
+            Error::Failed(count) => write!(f, "{} part(s) failed to solve", count),
+            Error::MissingBaseline(path) => {
+                write!(f, "no benchmark baseline saved at {}", path.display())
+            }
+            Error::InvalidBaseline(path, message) => {
+                write!(
+                    f,
+                    "invalid benchmark baseline {}: {}",
+                    path.display(),
+                    message
+                )
+            }
+            Error::Io(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        Error::Fetch(err.to_string())
+    }
+}
+
+/// Location and reason of a malformed puzzle input.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Builds the error for the byte `offset` of `input`.
+    pub fn at(input: &str, offset: usize, message: impl Into<String>) -> Self {
+        let offset = offset.min(input.len());
+        let line_start = input[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |idx| offset + idx);
+
+        Self {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            snippet: input[line_start..line_end].to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Locates a nom error, whose remaining input is a suffix of `input`.
+    pub fn from_nom(input: &str, err: nom::Err<nom::error::Error<&str>>) -> Self {
+        match err {
+            nom::Err::Error(err) | nom::Err::Failure(err) => Self::at(
+                input,
+                input.len() - err.input.len(),
+                format!("unexpected input, expected {}", err.code.description()),
+            ),
+            nom::Err::Incomplete(_) => Self::at(input, input.len(), "unexpected end of input"),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+
+        writeln!(
+            f,
+            "failed to parse input at line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.snippet)?;
+        write!(f, "{} | {}^", gutter, " ".repeat(self.column - 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_at() {
+        let input = "3   4\n4   x\n2   5\n";
+
+        let err = ParseError::at(input, 10, "expected a number");
+        assert_eq!(err.line, 2);
+        assert_eq!(err.column, 5);
+        assert_eq!(err.snippet, "4   x");
+
+        assert_eq!(
+            err.to_string(),
+            "failed to parse input at line 2, column 5: expected a number\n  |\n2 | 4   x\n  |     ^"
+        );
+    }
+
+    #[test]
+    fn test_parse_error_at_end() {
+        let err = ParseError::at("1,2\n", 4, "unexpected end of input");
+
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.snippet, "");
+    }
+
+    #[test]
+    fn test_parse_error_from_nom() {
+        let input = "12|34\n56-78";
+        let err =
+            nom::bytes::complete::tag::<&str, &str, nom::error::Error<&str>>("|")(&input[8..])
+                .unwrap_err();
+        let err = ParseError::from_nom(input, err);
+
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.message, "unexpected input, expected Tag");
+    }
+}
@@ -0,0 +1,364 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use nom::{
+    error::{Error as NomError, ErrorKind},
+    IResult,
+};
+
+use crate::{
+    error::ParseError,
+    geometry::{Direction, Point, Vector},
+    Error, Result,
+};
+
+/// Rectangular map of cells stored row by row.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Builds a grid from rows of the same length, `None` if they are ragged or empty.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first()?.len();
+        if width == 0 || rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        Some(Self {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses `input` as a grid, mapping every character with `cell`.
+    pub fn parse(input: &str, cell: impl FnMut(char) -> Option<T>) -> Result<Self> {
+        let start = input.trim_start_matches(['\n', '\r']);
+        let (remaining, grid) =
+            parser(cell)(start).map_err(|err| ParseError::from_nom(input, err))?;
+
+        let trailing = remaining.trim_start();
+        if !trailing.is_empty() {
+            let offset = input.len() - trailing.len();
+            return Err(ParseError::at(input, offset, "unexpected trailing input").into());
+        }
+
+        Ok(grid)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (0..self.width as i64).contains(&point.x) && (0..self.height as i64).contains(&point.y)
+    }
+
+    fn offset(&self, point: Point) -> Option<usize> {
+        self.contains(point)
+            .then(|| point.y as usize * self.width + point.x as usize)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.offset(point).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.offset(point).map(|offset| &mut self.cells[offset])
+    }
+
+    /// Every point of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width as i64;
+        (0..self.height as i64).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    /// The orthogonal neighbours of `point` that are inside the grid.
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ALL
+            .into_iter()
+            .map(move |direction| point + direction)
+            .filter(|neighbour| self.contains(*neighbour))
+    }
+
+    /// The orthogonal and diagonal neighbours of `point` that are inside the grid.
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        Vector::ALL_DIRECTIONS
+            .into_iter()
+            .map(move |offset| point + offset)
+            .filter(|neighbour| self.contains(*neighbour))
+    }
+
+    /// Cells from `start` walking by `step` until leaving the grid, `start` included.
+    pub fn ray(&self, start: Point, step: Vector) -> impl Iterator<Item = (Point, &T)> {
+        std::iter::successors(Some(start), move |point| Some(*point + step))
+            .map_while(|point| self.get(point).map(|cell| (point, cell)))
+    }
+
+    /// Cells of row `y`, `None` if it is outside of the grid.
+    pub fn row(&self, y: usize) -> Option<impl Iterator<Item = &T>> {
+        (y < self.height).then(|| self.cells[y * self.width..(y + 1) * self.width].iter())
+    }
+
+    /// Cells of column `x`, `None` if it is outside of the grid.
+    pub fn column(&self, x: usize) -> Option<impl Iterator<Item = &T>> {
+        (x < self.width).then(|| self.cells.iter().skip(x).step_by(self.width))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    /// Cells of the diagonal going down-right from `start`.
+    pub fn diagonal(&self, start: Point) -> impl Iterator<Item = &T> {
+        self.ray(start, Vector::new(1, 1)).map(|(_, cell)| cell)
+    }
+
+    /// Cells of the diagonal going down-left from `start`.
+    pub fn anti_diagonal(&self, start: Point) -> impl Iterator<Item = &T> {
+        self.ray(start, Vector::new(-1, 1)).map(|(_, cell)| cell)
+    }
+
+    pub fn find(&self, value: &T) -> Option<Point>
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .find(|(_, cell)| *cell == value)
+            .map(|(point, _)| point)
+    }
+
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Point> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(point, _)| point)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        match self.get(point) {
+            Some(cell) => cell,
+            None => panic!(
+                "{:?} is outside of the {}x{} grid",
+                point, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(point) {
+            Some(cell) => cell,
+            None => panic!("{:?} is outside of the {}x{} grid", point, width, height),
+        }
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        Self::parse(input, Some)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// nom parser of a block of non-empty rows of the same length, stopping at a blank line.
+///
+/// A character rejected by `cell` or a ragged row fails at its position.
+pub fn parser<'a, T>(
+    mut cell: impl FnMut(char) -> Option<T>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Grid<T>> {
+    move |input: &'a str| {
+        let mut cells = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+        let mut rest = input;
+
+        loop {
+            let row = &rest[..rest.find('\n').unwrap_or(rest.len())];
+            let row = row.strip_suffix('\r').unwrap_or(row);
+            if row.is_empty() {
+                break;
+            }
+
+            let before = cells.len();
+            for (idx, ch) in row.char_indices() {
+                match cell(ch) {
+                    Some(value) => cells.push(value),
+                    None => {
+                        return Err(nom::Err::Failure(NomError::new(
+                            &rest[idx..],
+                            ErrorKind::Char,
+                        )))
+                    }
+                }
+            }
+
+            if height > 0 && cells.len() - before != width {
+                return Err(nom::Err::Failure(NomError::new(rest, ErrorKind::Verify)));
+            }
+
+            width = cells.len() - before;
+            height += 1;
+            rest = &rest[row.len()..];
+
+            match rest.trim_start_matches('\r').strip_prefix('\n') {
+                Some(next) if !next.is_empty() && !next.starts_with(['\n', '\r']) => rest = next,
+                _ => break,
+            }
+        }
+
+        if height == 0 {
+            return Err(nom::Err::Error(NomError::new(input, ErrorKind::Many1)));
+        }
+
+        Ok((
+            rest,
+            Grid {
+                width,
+                height,
+                cells,
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TESTCASE: &str = "
+abc
+def
+";
+
+    #[test]
+    fn test_grid_from_str() {
+        let grid: Grid<char> = TESTCASE.parse().unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(2, 1)], 'f');
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, -1)), None);
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+
+        match "ab\nc\n".parse::<Grid<char>>() {
+            Err(Error::Parse(err)) => assert_eq!((err.line, err.column), (2, 1)),
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        match Grid::parse("..\n.x\n", |ch| (ch == '.').then_some(0)) {
+            Err(Error::Parse(err)) => assert_eq!((err.line, err.column), (2, 2)),
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_grid_parser_stops_at_blank_line() {
+        let (rest, grid) = parser(Some)("ab\ncd\n\nrest").unwrap();
+
+        assert_eq!(grid.height(), 2);
+        assert_eq!(rest, "\n\nrest");
+    }
+
+    #[test]
+    fn test_grid_neighbours() {
+        let grid: Grid<char> = TESTCASE.parse().unwrap();
+
+        assert_eq!(
+            grid.neighbours4(Point::new(0, 0)).collect::<Vec<_>>(),
+            vec![Point::new(1, 0), Point::new(0, 1)]
+        );
+        assert_eq!(grid.neighbours4(Point::new(1, 1)).count(), 3);
+        assert_eq!(grid.neighbours8(Point::new(1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn test_grid_iterators() {
+        let grid: Grid<char> = TESTCASE.parse().unwrap();
+
+        assert_eq!(grid.row(1).unwrap().collect::<String>(), "def");
+        assert_eq!(grid.column(2).unwrap().collect::<String>(), "cf");
+        assert!(grid.row(2).is_none());
+        assert!(grid.column(3).is_none());
+        assert_eq!(grid.diagonal(Point::new(0, 0)).collect::<String>(), "ae");
+        assert_eq!(
+            grid.anti_diagonal(Point::new(2, 0)).collect::<String>(),
+            "ce"
+        );
+        assert_eq!(
+            grid.ray(Point::new(2, 1), Direction::Left.vector())
+                .map(|(_, ch)| ch)
+                .collect::<String>(),
+            "fed"
+        );
+
+        assert_eq!(grid.find(&'e'), Some(Point::new(1, 1)));
+        assert_eq!(grid.find(&'z'), None);
+        assert_eq!(
+            grid.find_all(&'a').collect::<Vec<_>>(),
+            vec![Point::new(0, 0)]
+        );
+        assert_eq!(
+            grid.map(|ch| ch.is_ascii_lowercase())
+                .find_all(&true)
+                .count(),
+            6
+        );
+    }
+}
@@ -1 +1,15 @@
-pub mod puzzles;
\ No newline at end of file
+pub mod answers;
+pub mod bench;
+pub mod cache;
+pub mod client;
+pub mod error;
+pub mod geometry;
+pub mod grid;
+pub mod pathfinding;
+pub mod puzzles;
+pub mod runner;
+
+pub use error::{Error, Result};
+
+/// Year of the event, shared by the website URLs, the input cache and the answers file.
+pub const YEAR: i32 = 2024;
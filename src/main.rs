@@ -1,127 +1,468 @@
-use aoc2024::puzzles::*;
-use clap::{Parser, Subcommand};
-use reqwest::{
-    header::{HeaderMap, HeaderValue},
-    Client,
+use aoc2024::{
+    answers::{AnswerStore, Verdict},
+    bench::{self, Baseline, BenchOptions},
+    cache::InputCache,
+    client::{AocClient, SubmitOutcome},
+    puzzles::{
+        self,
+        day14::{Day14, Detector},
+        day17::{trace::TraceOptions, Day17},
+        day2::Day2,
+        Answer,
+    },
+    runner::{self, DayRange, Format},
+    Error, Result,
 };
-use std::io::prelude::*;
-use std::{error::Error, fs::File};
+use clap::{Args, Parser, Subcommand};
+use std::{fs, io, path::PathBuf, process::ExitCode};
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    #[command(flatten)]
+    input: InputOptions,
+}
+
+#[derive(Args)]
+struct InputOptions {
+    /// Download the input again even if it is already cached
+    #[arg(long, global = true, conflicts_with = "offline")]
+    refresh: bool,
+
+    /// Never touch the network, fail if the input is not cached
+    #[arg(long, global = true)]
+    offline: bool,
+}
+
+#[derive(Args)]
+#[group(multiple = false)]
+struct SourceOptions {
+    /// Read the input from a file instead of the cache
+    #[arg(long, value_name = "PATH")]
+    input: Option<PathBuf>,
+
+    /// Read the input from stdin instead of the cache
+    #[arg(long)]
+    stdin: bool,
 }
 
 #[derive(Subcommand)]
 enum Commands {
-    Day { day: i32 },
+    Day {
+        day: i32,
+
+        /// Only solve this part
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        #[command(flatten)]
+        source: SourceOptions,
+    },
+    /// Submit an answer, computing it from the puzzle when omitted
+    Submit {
+        day: i32,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        answer: Option<String>,
+    },
+    /// List the days with an implemented puzzle
+    List,
+    /// Run several days and print a table of answers and timings
+    Run {
+        /// Days to run, e.g. `5`, `1..10` or `1..=10`
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        days: Option<DayRange>,
+
+        /// Run every implemented day
+        #[arg(long)]
+        all: bool,
+
+        /// How to print the results
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+    /// Check the answers of every day against the recorded ones
+    Verify {
+        /// Days to verify, all of them when omitted
+        days: Option<DayRange>,
+
+        /// Record the answers of parts that have none yet
+        #[arg(long)]
+        record: bool,
+    },
+    /// Benchmark both parts of each day against its real input
+    Bench {
+        /// Days to benchmark, all of them when omitted
+        days: Option<DayRange>,
+
+        /// Runs before measuring
+        #[arg(long, default_value_t = BenchOptions::default().warmup)]
+        warmup: usize,
+
+        /// Measured runs
+        #[arg(long, default_value_t = BenchOptions::default().iterations)]
+        iterations: usize,
+
+        /// Save the results as a baseline under `target/bench/`
+        #[arg(long, value_name = "NAME")]
+        save_baseline: Option<String>,
+
+        /// Compare the results with a saved baseline
+        #[arg(long, value_name = "NAME")]
+        baseline: Option<String>,
+    },
+    /// Count the safe reports of Day 2 when some levels may be removed
+    Day2 {
+        #[command(flatten)]
+        source: SourceOptions,
+
+        /// Levels that may be removed from each report
+        #[arg(long, default_value_t = 1)]
+        tolerate: usize,
+
+        /// Print the verdict on every report
+        #[arg(long)]
+        explain: bool,
+    },
+    /// Tools for the robots of Day 14
+    Day14 {
+        #[command(subcommand)]
+        command: Day14Command,
+    },
+    /// Tools for the programs of the Day 17 three-bit computer
+    Day17 {
+        #[command(subcommand)]
+        command: Day17Command,
+    },
+}
+
+#[derive(Subcommand)]
+enum Day14Command {
+    /// Print the map of the robots, `x` marking tiles with robots on them
+    Frame {
+        #[command(flatten)]
+        source: SourceOptions,
+
+        /// Seconds after the start, the robots drawing their picture when omitted
+        #[arg(long)]
+        seconds: Option<i64>,
+    },
+}
+
+#[derive(Subcommand)]
+enum Day17Command {
+    /// Print the program as mnemonics, with pseudo-code for its loop
+    Disasm {
+        #[command(flatten)]
+        source: SourceOptions,
+    },
+    /// Run the program and print every step as a table
+    Trace {
+        #[command(flatten)]
+        source: SourceOptions,
+
+        /// Stop before executing this address again, may be repeated
+        #[arg(long = "break", value_name = "ADDRESS")]
+        breakpoints: Vec<u64>,
+
+        /// Stop after this many steps if the program has not halted
+        #[arg(long, default_value_t = TraceOptions::default().max_steps)]
+        max_steps: usize,
+    },
+}
+
+fn get_puzzle(day: i32) -> Result<&'static dyn puzzles::Puzzle> {
+    puzzles::get(day).ok_or(Error::UnsupportedDay(day))
 }
 
-async fn get_input(day: i32) -> Result<String, Box<dyn Error>> {
-    let url = format!("https://adventofcode.com/2024/day/{:?}/input", day);
-    let mut cookies = String::new();
-    File::open(".cookie")?.read_to_string(&mut cookies)?;
+fn select_days(range: Option<DayRange>) -> Result<Vec<i32>> {
+    let days: Vec<i32> = puzzles::days()
+        .filter(|day| range.is_none_or(|range| range.contains(*day)))
+        .collect();
+
+    match range {
+        Some(range) if days.is_empty() => Err(Error::UnsupportedDay(range.first)),
+        _ => Ok(days),
+    }
+}
+
+fn client() -> Result<AocClient> {
+    AocClient::from_cookie_file(".cookie")
+}
 
-    let mut headers = HeaderMap::new();
-    headers.insert("COOKIE", HeaderValue::from_str(&cookies)?);
-    let client = Client::builder().default_headers(headers).build()?;
+async fn get_input(day: i32, options: &InputOptions) -> Result<String> {
+    let cache = InputCache::default();
+
+    if !options.refresh {
+        if let Some(input) = cache.load(day)? {
+            return Ok(input);
+        }
+    }
+
+    if options.offline {
+        return Err(Error::NotCached(cache.path(day)));
+    }
+
+    let input = client()?.fetch_input(day).await?;
+    cache.store(day, &input)?;
+
+    Ok(input)
+}
 
-    let resp = client.get(url).send().await?;
-    assert_eq!(
-        resp.status(),
-        200,
-        "Error getting puzzle import. Message: {:#?}",
-        resp.text().await?
-    );
+/// Input of `day` for the commands going over several days, `None` when it is skipped for not
+/// being cached with `--offline`.
+///
+/// The skip is reported on stderr, so the JSON and CSV output stay parseable.
+async fn input_or_skip(day: i32, options: &InputOptions) -> Result<Option<String>> {
+    match get_input(day, options).await {
+        Ok(data) => Ok(Some(data)),
+        Err(Error::NotCached(path)) => {
+            eprintln!("Day {}: skipped, {} is not cached", day, path.display());
+            Ok(None)
+        }
+        Err(err) => Err(err),
+    }
+}
 
-    Ok(resp.text().await?)
+async fn read_input(day: i32, source: &SourceOptions, options: &InputOptions) -> Result<String> {
+    match source {
+        SourceOptions {
+            input: Some(path), ..
+        } => fs::read_to_string(path).map_err(|err| {
+            io::Error::new(
+                err.kind(),
+                format!("cannot read {}: {}", path.display(), err),
+            )
+            .into()
+        }),
+        SourceOptions { stdin: true, .. } => Ok(io::read_to_string(io::stdin())?),
+        _ => get_input(day, options).await,
+    }
 }
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn Error>> {
+async fn main() -> ExitCode {
     let cli = Cli::parse();
 
+    match run(&cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: &Cli) -> Result<()> {
     match &cli.command {
-        Commands::Day { day } => {
-            let data = get_input(day.clone()).await?;
+        Commands::Day { day, part, source } => {
+            let puzzle = get_puzzle(*day)?;
+            let data = read_input(*day, source, &cli.input).await?;
 
-            match day {
-                1 => {
-                    let puzzle = day1::Day1;
-                    puzzle.solve(&data);
-                }
-                2 => {
-                    let puzzle = day2::Day2;
-                    puzzle.solve(&data);
-                }
-                3 => {
-                    let puzzle = day3::Day3;
-                    puzzle.solve(&data);
-                }
-                4 => {
-                    let puzzle = day4::Day4;
-                    puzzle.solve(&data);
-                }
-                5 => {
-                    let puzzle = day5::Day5;
-                    puzzle.solve(&data);
-                }
-                6 => {
-                    let puzzle = day6::Day6;
-                    puzzle.solve(&data);
-                }
-                7 => {
-                    let puzzle = day7::Day7;
-                    puzzle.solve(&data);
-                }
-                8 => {
-                    let puzzle = day8::Day8;
-                    puzzle.solve(&data);
-                }
-                9 => {
-                    let puzzle = day9::Day9;
-                    puzzle.solve(&data);
-                }
-                10 => {
-                    let puzzle = day10::Day10;
-                    puzzle.solve(&data);
-                }
-                11 => {
-                    let puzzle = day11::Day11;
-                    puzzle.solve(&data);
-                }
-                12 => {
-                    let puzzle = day12::Day12;
-                    puzzle.solve(&data);
-                }
-                13 => {
-                    let puzzle = day13::Day13;
-                    puzzle.solve(&data);
-                }
-                14 => {
-                    let puzzle = day14::Day14;
-                    puzzle.solve(&data);
-                }
-                15 => {
-                    let puzzle = day15::Day15;
-                    puzzle.solve(&data);
+            match part {
+                Some(part) => puzzle.solve_part(*part, &data)?,
+                None => puzzle.solve(&data)?,
+            }
+        }
+
+        Commands::Submit { day, part, answer } => {
+            let answer = match answer {
+                Some(answer) => answer.clone(),
+                None => {
+                    let puzzle = get_puzzle(*day)?;
+                    let data = get_input(*day, &cli.input).await?;
+
+                    match puzzle.part(*part, &data)? {
+                        Answer::Unsolved => {
+                            return Err(Error::Solve(format!(
+                                "day {} part {} is not solved yet",
+                                day, part
+                            )))
+                        }
+                        answer => answer.to_string(),
+                    }
                 }
-                16 => {
-                    let puzzle = day16::Day16;
-                    puzzle.solve(&data);
+            };
+
+            println!("Submitting {} for Day {} Part {}", answer, day, part);
+            let outcome = client()?.submit(*day, *part, &answer).await?;
+            println!("{}", outcome);
+
+            if outcome == SubmitOutcome::Correct {
+                let mut store = AnswerStore::load(AnswerStore::default_path())?;
+                store.record(*day, *part, answer);
+                store.save()?;
+            }
+
+            if let SubmitOutcome::Unrecognized(_) = outcome {
+                return Err(Error::Fetch(String::from(
+                    "could not understand the response of the server",
+                )));
+            }
+        }
+
+        Commands::List => {
+            for day in puzzles::days() {
+                println!("Day {}", day);
+            }
+        }
+
+        Commands::Run {
+            days: range,
+            all: _,
+            format,
+        } => {
+            let days = select_days(*range)?;
+
+            let mut reports = Vec::with_capacity(days.len());
+            for day in days {
+                let puzzle = get_puzzle(day)?;
+                let Some(data) = input_or_skip(day, &cli.input).await? else {
+                    continue;
+                };
+
+                reports.push(runner::run_day(puzzle, &data));
+            }
+
+            println!("{}", runner::render(&reports, *format));
+        }
+
+        Commands::Verify { days, record } => {
+            let mut store = AnswerStore::load(AnswerStore::default_path())?;
+            let mut failures = 0;
+            let mut recorded = 0;
+
+            for day in select_days(*days)? {
+                let puzzle = get_puzzle(day)?;
+                let Some(data) = input_or_skip(day, &cli.input).await? else {
+                    continue;
+                };
+                let report = runner::run_day(puzzle, &data);
+
+                for (part, verdict) in (1..).zip(store.verify(&report)) {
+                    println!("Day {} Part {}: {}", day, part, verdict);
+
+                    match verdict {
+                        Verdict::Unrecorded(Some(answer)) if *record => {
+                            store.record(day, part, answer);
+                            recorded += 1;
+                        }
+                        verdict if verdict.is_failure() => failures += 1,
+                        _ => {}
+                    }
                 }
-                17 => {
-                    let puzzle = day17::Day17;
-                    puzzle.solve(&data);
+            }
+
+            if recorded > 0 {
+                store.save()?;
+                println!(
+                    "Recorded {} answer(s) in {}",
+                    recorded,
+                    store.path().display()
+                );
+            }
+
+            if failures > 0 {
+                return Err(Error::Mismatch(failures));
+            }
+        }
+
+        Commands::Bench {
+            days,
+            warmup,
+            iterations,
+            save_baseline,
+            baseline,
+        } => {
+            let baseline = baseline.as_deref().map(Baseline::load).transpose()?;
+            let options = BenchOptions {
+                warmup: *warmup,
+                iterations: *iterations,
+            };
+
+            let mut results = vec![];
+            for day in select_days(*days)? {
+                let puzzle = get_puzzle(day)?;
+                let Some(data) = input_or_skip(day, &cli.input).await? else {
+                    continue;
+                };
+
+                for part in 1..=2 {
+                    match bench::bench_part(puzzle, part, &data, options) {
+                        Ok(result) => results.push(result),
+                        Err(err) => eprintln!("Day {} Part {}: {}", day, part, err),
+                    }
                 }
+            }
 
-                _ => {
-                    println!("Puzzle of day {:#?} not found!", day);
+            println!("{}", bench::render(&results, baseline.as_ref()));
+
+            if let Some(name) = save_baseline {
+                Baseline { results }.save(name)?;
+                println!("Saved baseline to {}", Baseline::path(name).display());
+            }
+        }
+
+        Commands::Day2 {
+            source,
+            tolerate,
+            explain,
+        } => {
+            let data = read_input(2, source, &cli.input).await?;
+
+            let diagnoses = Day2.diagnose(&data, *tolerate)?;
+            if *explain {
+                for diagnosis in &diagnoses {
+                    println!("{}", diagnosis);
                 }
             }
+
+            let safe = diagnoses
+                .iter()
+                .filter(|diagnosis| diagnosis.verdict.is_safe())
+                .count();
+            println!(
+                "{} of {} reports are safe with up to {} level(s) removed",
+                safe,
+                diagnoses.len(),
+                tolerate
+            );
         }
+
+        Commands::Day14 { command } => match command {
+            Day14Command::Frame { source, seconds } => {
+                let data = read_input(14, source, &cli.input).await?;
+                let seconds = match seconds {
+                    Some(seconds) => *seconds,
+                    None => Day14.seconds_to_picture(&data, Detector::Variance)?,
+                };
+
+                println!("After {} seconds:", seconds);
+                println!("{}", Day14.render_frame(&data, seconds)?);
+            }
+        },
+
+        Commands::Day17 { command } => match command {
+            Day17Command::Disasm { source } => {
+                let data = read_input(17, source, &cli.input).await?;
+                println!("{}", Day17.disassemble(&data)?);
+            }
+            Day17Command::Trace {
+                source,
+                breakpoints,
+                max_steps,
+            } => {
+                let data = read_input(17, source, &cli.input).await?;
+                let options = TraceOptions {
+                    max_steps: *max_steps,
+                    breakpoints: breakpoints.clone(),
+                };
+                println!("{}", Day17.trace(&data, &options)?);
+            }
+        },
     };
 
     Ok(())
@@ -0,0 +1,293 @@
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+/// Cost of moving between two states.
+pub type Cost = u64;
+
+/// Walks `predecessors` back from `goal` and returns the states from the start to `goal`.
+fn rebuild_path<S: Clone + Eq + Hash>(predecessors: &HashMap<S, S>, goal: S) -> Vec<S> {
+    let mut path = vec![goal];
+    while let Some(previous) = predecessors.get(path.last().unwrap()) {
+        path.push(previous.clone());
+    }
+    path.reverse();
+
+    path
+}
+
+/// Breadth-first search, returns the states of a path with the fewest steps to a goal.
+pub fn bfs<S, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Vec<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut predecessors = HashMap::new();
+    let mut seen = HashSet::from([start.clone()]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(state) = queue.pop_front() {
+        if is_goal(&state) {
+            return Some(rebuild_path(&predecessors, state));
+        }
+
+        for next in neighbours(&state) {
+            if seen.insert(next.clone()) {
+                predecessors.insert(next.clone(), state.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Dijkstra's algorithm, returns the cheapest path to a goal and its cost.
+pub fn dijkstra<S, I>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<(Vec<S>, Cost)>
+where
+    S: Clone + Ord + Hash,
+    I: IntoIterator<Item = (S, Cost)>,
+{
+    astar(start, neighbours, |_| 0, is_goal)
+}
+
+/// A* search, `heuristic` must never overestimate the remaining cost to a goal.
+pub fn astar<S, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> Cost,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<(Vec<S>, Cost)>
+where
+    S: Clone + Ord + Hash,
+    I: IntoIterator<Item = (S, Cost)>,
+{
+    let mut costs = HashMap::from([(start.clone(), 0)]);
+    let mut predecessors = HashMap::new();
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), 0, start))]);
+
+    while let Some(Reverse((_, cost, state))) = heap.pop() {
+        if costs.get(&state).is_some_and(|best| *best < cost) {
+            continue;
+        }
+
+        if is_goal(&state) {
+            return Some((rebuild_path(&predecessors, state), cost));
+        }
+
+        for (next, step) in neighbours(&state) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_some_and(|best| *best <= next_cost) {
+                continue;
+            }
+
+            costs.insert(next.clone(), next_cost);
+            predecessors.insert(next.clone(), state.clone());
+            heap.push(Reverse((next_cost + heuristic(&next), next_cost, next)));
+        }
+    }
+
+    None
+}
+
+/// Dijkstra's algorithm without a goal, returns the cost of every reachable state.
+pub fn dijkstra_all<S, I>(starts: &[S], mut neighbours: impl FnMut(&S) -> I) -> HashMap<S, Cost>
+where
+    S: Clone + Ord + Hash,
+    I: IntoIterator<Item = (S, Cost)>,
+{
+    let mut costs: HashMap<S, Cost> = starts.iter().map(|start| (start.clone(), 0)).collect();
+    let mut heap: BinaryHeap<_> = starts
+        .iter()
+        .map(|start| Reverse((0, start.clone())))
+        .collect();
+
+    while let Some(Reverse((cost, state))) = heap.pop() {
+        if costs.get(&state).is_some_and(|best| *best < cost) {
+            continue;
+        }
+
+        for (next, step) in neighbours(&state) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_some_and(|best| *best <= next_cost) {
+                continue;
+            }
+
+            costs.insert(next.clone(), next_cost);
+            heap.push(Reverse((next_cost, next)));
+        }
+    }
+
+    costs
+}
+
+/// Every cheapest path to the goals, kept as a DAG of predecessors.
+pub struct ShortestPaths<S> {
+    pub cost: Cost,
+    pub goals: Vec<S>,
+    pub predecessors: HashMap<S, Vec<S>>,
+}
+
+impl<S: Clone + Eq + Hash> ShortestPaths<S> {
+    /// States lying on at least one cheapest path, start and goals included.
+    pub fn states(&self) -> HashSet<S> {
+        let mut states: HashSet<S> = self.goals.iter().cloned().collect();
+        let mut stack = self.goals.clone();
+
+        while let Some(state) = stack.pop() {
+            for previous in self.predecessors.get(&state).into_iter().flatten() {
+                if states.insert(previous.clone()) {
+                    stack.push(previous.clone());
+                }
+            }
+        }
+
+        states
+    }
+}
+
+/// Dijkstra's algorithm keeping every predecessor reached at the cheapest cost.
+pub fn all_shortest_paths<S, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<ShortestPaths<S>>
+where
+    S: Clone + Ord + Hash,
+    I: IntoIterator<Item = (S, Cost)>,
+{
+    let mut costs = HashMap::from([(start.clone(), 0)]);
+    let mut predecessors: HashMap<S, Vec<S>> = HashMap::new();
+    let mut heap = BinaryHeap::from([Reverse((0, start))]);
+    let mut found: Option<ShortestPaths<S>> = None;
+
+    while let Some(Reverse((cost, state))) = heap.pop() {
+        if costs.get(&state).is_some_and(|best| *best < cost) {
+            continue;
+        }
+
+        if let Some(found) = &mut found {
+            if cost > found.cost {
+                break;
+            }
+        }
+
+        if is_goal(&state) {
+            found
+                .get_or_insert_with(|| ShortestPaths {
+                    cost,
+                    goals: vec![],
+                    predecessors: HashMap::new(),
+                })
+                .goals
+                .push(state);
+            continue;
+        }
+
+        for (next, step) in neighbours(&state) {
+            let next_cost = cost + step;
+            match costs.entry(next.clone()) {
+                Entry::Occupied(entry) if *entry.get() < next_cost => continue,
+                Entry::Occupied(mut entry) if *entry.get() > next_cost => {
+                    entry.insert(next_cost);
+                    predecessors.insert(next.clone(), vec![state.clone()]);
+                    heap.push(Reverse((next_cost, next)));
+                }
+                Entry::Occupied(_) => {
+                    predecessors.entry(next).or_default().push(state.clone());
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(next_cost);
+                    predecessors.insert(next.clone(), vec![state.clone()]);
+                    heap.push(Reverse((next_cost, next)));
+                }
+            }
+        }
+    }
+
+    found.map(|found| ShortestPaths {
+        predecessors,
+        ..found
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 3 costs 1 + 5, 0 -> 2 -> 3 costs 2 + 4 and 0 -> 3 costs 7.
+    fn graph(state: &u32) -> Vec<(u32, Cost)> {
+        match state {
+            0 => vec![(1, 1), (2, 2), (3, 7)],
+            1 => vec![(3, 5)],
+            2 => vec![(3, 4)],
+            3 => vec![(4, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_pathfinding_bfs() {
+        let neighbours = |state: &u32| graph(state).into_iter().map(|(next, _)| next);
+
+        assert_eq!(bfs(0, neighbours, |state| *state == 4), Some(vec![0, 3, 4]));
+        assert_eq!(bfs(1, neighbours, |state| *state == 2), None);
+    }
+
+    #[test]
+    fn test_pathfinding_dijkstra() {
+        assert_eq!(
+            dijkstra(0, graph, |state| *state == 4),
+            Some((vec![0, 1, 3, 4], 7))
+        );
+        assert_eq!(dijkstra(4, graph, |state| *state == 0), None);
+
+        let costs = dijkstra_all(&[0], graph);
+        assert_eq!(costs[&3], 6);
+        assert_eq!(costs.len(), 5);
+    }
+
+    #[test]
+    fn test_pathfinding_astar() {
+        // Distance on a line with walls, the heuristic is the remaining distance.
+        let neighbours = |state: &i64| {
+            [state - 1, state + 1]
+                .into_iter()
+                .filter(|next| (0..=10).contains(next) && *next != 4)
+                .map(|next| (next, 1))
+        };
+
+        assert_eq!(
+            astar(
+                5,
+                neighbours,
+                |state| (10 - state) as Cost,
+                |state| *state == 10
+            )
+            .map(|(_, cost)| cost),
+            Some(5)
+        );
+        assert_eq!(astar(5, neighbours, |_| 0, |state| *state == 0), None);
+    }
+
+    #[test]
+    fn test_pathfinding_all_shortest_paths() {
+        let paths = all_shortest_paths(0, graph, |state| *state == 4).unwrap();
+
+        assert_eq!(paths.cost, 7);
+        assert_eq!(paths.goals, vec![4]);
+        assert_eq!(paths.states(), HashSet::from([0, 1, 2, 3, 4]));
+
+        assert!(all_shortest_paths(4, graph, |state| *state == 0).is_none());
+    }
+}
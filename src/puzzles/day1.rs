@@ -1,29 +1,52 @@
 use std::collections::HashMap;
 
-use super::Puzzle;
+use super::{parse_input, Answer, Solution};
+use crate::Result;
 
 pub struct Day1;
 
-impl Day1 {
-    fn parse(&self, input: &str) -> Vec<Vec<i32>> {
-        input
-            .trim()
-            .split("\n")
-            .map(|line| {
-                line.trim()
-                    .split("   ")
-                    .map(|level| level.parse().unwrap())
-                    .collect()
-            })
-            .collect()
+mod parser {
+    use nom::IResult;
+
+    fn parse_row(input: &str) -> IResult<&str, Vec<i32>> {
+        nom::combinator::map(
+            nom::sequence::separated_pair(
+                nom::character::complete::i32,
+                nom::character::complete::space1,
+                nom::character::complete::i32,
+            ),
+            |(left, right)| vec![left, right],
+        )(input)
+    }
+
+    /// Newline followed by another row, so blank lines at the end are left as trailing input.
+    fn row_separator(input: &str) -> IResult<&str, char> {
+        nom::sequence::terminated(
+            nom::character::complete::newline,
+            nom::combinator::peek(nom::character::complete::satisfy(|c| !c.is_whitespace())),
+        )(input)
+    }
+
+    pub fn parse(input: &str) -> IResult<&str, Vec<Vec<i32>>> {
+        // A row that starts but does not parse is a failure, so its error points at the bad
+        // token rather than at the start of the line.
+        nom::sequence::preceded(
+            nom::character::complete::multispace0,
+            nom::multi::separated_list1(row_separator, nom::combinator::cut(parse_row)),
+        )(input)
     }
 }
 
-impl Puzzle for Day1 {
-    type Output = i32;
+impl Solution for Day1 {
+    const DAY: i32 = 1;
+
+    type Input<'a> = Vec<Vec<i32>>;
 
-    fn part1(&self, input: &str) -> Self::Output {
-        let location_id_list = self.parse(input);
+    fn parse(&self, input: &str) -> Result<Vec<Vec<i32>>> {
+        parse_input(input, parser::parse)
+    }
+
+    fn solve1(&self, location_id_list: &Self::Input<'_>) -> Result<Answer> {
         let mut left = Vec::<i32>::new();
         let mut right = Vec::<i32>::new();
 
@@ -40,11 +63,10 @@ impl Puzzle for Day1 {
             total_distance += (left[idx] - right[idx]).abs();
         }
 
-        total_distance
+        Ok(total_distance.into())
     }
 
-    fn part2(&self, input: &str) -> Self::Output {
-        let location_id_list = self.parse(input);
+    fn solve2(&self, location_id_list: &Self::Input<'_>) -> Result<Answer> {
         let mut left = Vec::<i32>::new();
         let mut right = Vec::<i32>::new();
 
@@ -63,15 +85,7 @@ impl Puzzle for Day1 {
         left.iter()
             .for_each(|id| similarity_score += id * *similarity_map.entry(*id).or_insert(0));
 
-        similarity_score
-    }
-
-    fn solve(&self, input: &str) {
-        let ans1 = self.part1(&input);
-        println!("Answer of Day 1 Part 1:  {:#?}", ans1);
-
-        let ans2 = self.part2(&input);
-        println!("Answer of Day 1 Part 2:  {:#?}", ans2);
+        Ok(similarity_score.into())
     }
 }
 
@@ -80,8 +94,10 @@ mod tests {
     use std::vec;
 
     use super::*;
+    use crate::puzzles::Puzzle;
+    use crate::Error;
 
-    const TESTCASE: &'static str = r"3   4
+    const TESTCASE: &str = r"3   4
 4   3
 2   5
 1   3
@@ -101,18 +117,36 @@ mod tests {
             vec![3, 3],
         ];
 
-        assert_eq!(puzzle.parse(&TESTCASE), parse_result)
+        assert_eq!(puzzle.parse(TESTCASE).unwrap(), parse_result)
+    }
+
+    #[test]
+    fn test_puzzle_day1_parse_error() {
+        let puzzle = Day1;
+
+        match puzzle.parse("3   4\n4   3\n2   five\n") {
+            Err(Error::Parse(err)) => {
+                assert_eq!((err.line, err.column), (3, 5));
+                assert_eq!(err.snippet, "2   five");
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        match puzzle.parse("3   4\n4 x 3\n") {
+            Err(Error::Parse(err)) => assert_eq!((err.line, err.column), (2, 3)),
+            result => panic!("unexpected result {:?}", result),
+        }
     }
 
     #[test]
     fn test_puzzle_day1_part1() {
         let puzzle = Day1;
-        assert_eq!(puzzle.part1(&TESTCASE), 11);
+        assert_eq!(puzzle.part1(TESTCASE).unwrap(), 11.into());
     }
 
     #[test]
     fn test_puzzle_day1_part2() {
         let puzzle = Day1;
-        assert_eq!(puzzle.part2(&TESTCASE), 31);
+        assert_eq!(puzzle.part2(TESTCASE).unwrap(), 31.into());
     }
 }
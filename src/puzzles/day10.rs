@@ -1,160 +1,72 @@
 use std::collections::HashSet;
 
-use super::Puzzle;
+use super::{Answer, Solution};
+use crate::{geometry::Point, grid::Grid, Result};
 
 pub struct Day10;
 
-type Input = Vec<Vec<usize>>;
-type Positon = (usize, usize);
-
-mod parser {
-    use nom::IResult;
-
-    use super::Input;
-
-    pub fn parse(input: &str) -> IResult<&str, Input> {
-        nom::sequence::preceded(
-            nom::combinator::opt(nom::character::complete::newline),
-            nom::multi::separated_list1(
-                nom::character::complete::newline,
-                nom::multi::many1(nom::combinator::map_res(
-                    nom::character::complete::satisfy(|ch| ch.is_digit(10)),
-                    |c| Ok::<usize, &str>(c.to_digit(10).unwrap() as usize),
-                )),
-            ),
-        )(input)
-    }
-}
+type Input = Grid<u32>;
 
 impl Day10 {
-    fn parse(&self, input: &str) -> Result<Input, String> {
-        let (_, input) = parser::parse(input).map_err(|err| format!("{:#?}", err))?;
-
-        Ok(input)
+    fn next_steps<'a>(&self, map: &'a Input, position: Point) -> impl Iterator<Item = Point> + 'a {
+        let height = map[position];
+        map.neighbours4(position)
+            .filter(move |next| map[*next] == height + 1)
     }
 
-    fn count_trailhead_score(&self, map: &Vec<Vec<usize>>, position: Positon) -> HashSet<Positon> {
-        let height = map[position.0][position.1];
-        if height == 9 {
+    fn count_trailhead_score(&self, map: &Input, position: Point) -> HashSet<Point> {
+        if map[position] == 9 {
             return HashSet::from([position]);
         }
 
-        let m = map.len() as i32;
-        let n = map[0].len() as i32;
-
-        let r = position.0 as i32;
-        let c = position.1 as i32;
-
-        let mut highests = HashSet::new();
-
-        let directions = [(-1, 0), (1, 0), (0, -1), (0, 1)];
-        for (dr, dc) in directions {
-            if r + dr < 0 || r + dr > m - 1 || c + dc < 0 || c + dc > n - 1 {
-                continue;
-            }
-
-            let next_row = (r + dr) as usize;
-            let next_col = (c + dc) as usize;
-
-            if map[next_row][next_col] != height + 1 {
-                continue;
-            }
-
-            highests.extend(self.count_trailhead_score(map, (next_row, next_col)));
-        }
-
-        highests
+        self.next_steps(map, position)
+            .flat_map(|next| self.count_trailhead_score(map, next))
+            .collect()
     }
 
-    fn count_trailhead_rating(&self, map: &Vec<Vec<usize>>, position: Positon) -> usize {
-        let height = map[position.0][position.1];
-        if height == 9 {
+    fn count_trailhead_rating(&self, map: &Input, position: Point) -> usize {
+        if map[position] == 9 {
             return 1;
         }
 
-        let m = map.len() as i32;
-        let n = map[0].len() as i32;
-
-        let r = position.0 as i32;
-        let c = position.1 as i32;
-
-        let mut path_count = 0;
-
-        let directions = [(-1, 0), (1, 0), (0, -1), (0, 1)];
-        for (dr, dc) in directions {
-            if r + dr < 0 || r + dr > m - 1 || c + dc < 0 || c + dc > n - 1 {
-                continue;
-            }
-
-            let next_row = (r + dr) as usize;
-            let next_col = (c + dc) as usize;
-
-            if map[next_row][next_col] != height + 1 {
-                continue;
-            }
-
-            path_count += self.count_trailhead_rating(map, (next_row, next_col));
-        }
-
-        path_count
+        self.next_steps(map, position)
+            .map(|next| self.count_trailhead_rating(map, next))
+            .sum()
     }
 }
 
-impl Puzzle for Day10 {
-    type Output = Result<usize, String>;
-
-    fn part1(&self, input: &str) -> Self::Output {
-        let map = self.parse(input)?;
-
-        let trailheads: Vec<Positon> = map
-            .iter()
-            .enumerate()
-            .flat_map(|(row_idx, rows)| {
-                rows.iter()
-                    .enumerate()
-                    .filter(|(_, &height)| height == 0)
-                    .map(move |(col_idx, _)| (row_idx, col_idx))
-                    .collect::<Vec<Positon>>()
-            })
-            .collect();
-
-        Ok(trailheads
-            .iter()
-            .map(|&pos| self.count_trailhead_score(&map, pos).len())
-            .sum())
-    }
+impl Solution for Day10 {
+    const DAY: i32 = 10;
 
-    fn part2(&self, input: &str) -> Self::Output {
-        let map = self.parse(input)?;
+    type Input<'a> = Input;
 
-        Ok(map
-            .iter()
-            .enumerate()
-            .flat_map(|(row_idx, rows)| {
-                rows.iter()
-                    .enumerate()
-                    .filter(|(_, &height)| height == 0)
-                    .map(move |(col_idx, _)| (row_idx, col_idx))
-                    .collect::<Vec<Positon>>()
-            })
-            .map(|pos| self.count_trailhead_rating(&map, pos))
-            .sum())
+    fn parse(&self, input: &str) -> Result<Input> {
+        Grid::parse(input, |c| c.to_digit(10))
     }
 
-    fn solve(&self, input: &str) {
-        let ans1 = self.part1(&input);
-        println!("Answer of Day 10 Part 1: {:#?}", ans1.unwrap());
+    fn solve1(&self, map: &Self::Input<'_>) -> Result<Answer> {
+        Ok(map
+            .find_all(&0)
+            .map(|pos| self.count_trailhead_score(map, pos).len())
+            .sum::<usize>()
+            .into())
+    }
 
-        let ans2 = self.part2(&input);
-        println!("Answer of Day 10 Part 2: {:#?}", ans2.unwrap());
+    fn solve2(&self, map: &Self::Input<'_>) -> Result<Answer> {
+        Ok(map
+            .find_all(&0)
+            .map(|pos| self.count_trailhead_rating(map, pos))
+            .sum::<usize>()
+            .into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::puzzles::Puzzle;
 
-    const TESTCASE: &'static str = r"
+    const TESTCASE: &str = r"
 89010123
 78121874
 87430965
@@ -178,18 +90,21 @@ mod tests {
             vec![1, 0, 4, 5, 6, 7, 3, 2],
         ];
 
-        assert_eq!(puzzle.parse(&TESTCASE).unwrap(), topographic_map);
+        assert_eq!(
+            puzzle.parse(TESTCASE).unwrap(),
+            Grid::from_rows(topographic_map).unwrap()
+        );
     }
 
     #[test]
     fn test_puzzle_day10_part1() {
         let puzzle = Day10;
-        assert_eq!(puzzle.part1(&TESTCASE).unwrap(), 36);
+        assert_eq!(puzzle.part1(TESTCASE).unwrap(), 36.into());
     }
 
     #[test]
     fn test_puzzle_day10_part2() {
         let puzzle = Day10;
-        assert_eq!(puzzle.part2(&TESTCASE).unwrap(), 81);
+        assert_eq!(puzzle.part2(TESTCASE).unwrap(), 81.into());
     }
 }
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
-use super::Puzzle;
+use super::{parse_input, Answer, Solution};
+use crate::Result;
 
 pub struct Day11;
 
@@ -23,12 +24,6 @@ mod parser {
 }
 
 impl Day11 {
-    fn parse(&self, input: &str) -> Result<Input, String> {
-        let (_, input) = parser::parse(input).map_err(|err| format!("{:#?}", err))?;
-
-        Ok(input)
-    }
-
     fn count_digits(&self, stone: &u64) -> u64 {
         let mut digits = 0;
         let mut stone = *stone;
@@ -44,7 +39,7 @@ impl Day11 {
     fn next(&self, stones: u64) -> Vec<u64> {
         if stones == 0 {
             vec![1]
-        } else if self.count_digits(&stones) % 2 == 0 {
+        } else if self.count_digits(&stones).is_multiple_of(2) {
             let digits = self.count_digits(&stones);
             let half_digits = digits as u32 / 2;
 
@@ -59,7 +54,7 @@ impl Day11 {
 
     fn blink(
         &self,
-        arrangement: Input,
+        arrangement: &[u64],
         transform_map: &mut HashMap<usize, HashMap<u64, usize>>,
         iter: usize,
     ) -> usize {
@@ -77,7 +72,7 @@ impl Day11 {
                     return *count;
                 }
 
-                let count = self.blink(self.next(*stones), transform_map, iter - 1);
+                let count = self.blink(&self.next(*stones), transform_map, iter - 1);
 
                 transform_map
                     .entry(iter)
@@ -91,50 +86,47 @@ impl Day11 {
     }
 }
 
-impl Puzzle for Day11 {
-    type Output = Result<usize, String>;
+impl Solution for Day11 {
+    const DAY: i32 = 11;
 
-    fn part1(&self, input: &str) -> Self::Output {
-        let arrangement = self.parse(input)?;
-        let mut transform_map = HashMap::new();
+    type Input<'a> = Input;
 
-        Ok(self.blink(arrangement, &mut transform_map, 25))
+    fn parse(&self, input: &str) -> Result<Input> {
+        parse_input(input, parser::parse)
     }
 
-    fn part2(&self, input: &str) -> Self::Output {
-        let arrangement = self.parse(input)?;
+    fn solve1(&self, arrangement: &Self::Input<'_>) -> Result<Answer> {
         let mut transform_map = HashMap::new();
 
-        Ok(self.blink(arrangement, &mut transform_map, 75))
+        Ok(self.blink(arrangement, &mut transform_map, 25).into())
     }
 
-    fn solve(&self, input: &str) {
-        let ans1 = self.part1(&input);
-        println!("Answer of Day 11 Part 1: {:#?}", ans1.unwrap());
+    fn solve2(&self, arrangement: &Self::Input<'_>) -> Result<Answer> {
+        let mut transform_map = HashMap::new();
 
-        let ans2 = self.part2(&input);
-        println!("Answer of Day 11 Part 2: {:#?}", ans2.unwrap());
+        Ok(self.blink(arrangement, &mut transform_map, 75).into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::puzzles::Puzzle;
 
-    const TESTCASE: &'static str = r"125 17";
+    const TESTCASE: &str = r"125 17";
 
     #[test]
     fn test_puzzle_day11_parse() {
         let puzzle = Day11;
         let stones = vec![125, 17];
 
-        assert_eq!(puzzle.parse(&TESTCASE).unwrap(), stones);
+        assert_eq!(puzzle.parse(TESTCASE).unwrap(), stones);
     }
 
     #[test]
     fn test_puzzle_day11_part1() {
         let puzzle = Day11;
-        assert_eq!(puzzle.part1(&TESTCASE).unwrap(), 55312);
+        assert_eq!(puzzle.part1(TESTCASE).unwrap(), 55312.into());
     }
 
     #[test]
@@ -142,6 +134,6 @@ mod tests {
     fn test_puzzle_day11_part2() {
         let puzzle = Day11;
 
-        assert_eq!(puzzle.part2(&TESTCASE).unwrap(), 0);
+        assert_eq!(puzzle.part2(TESTCASE).unwrap(), 0.into());
     }
 }
@@ -1,211 +1,133 @@
 use std::collections::HashSet;
 
-use super::Puzzle;
+use super::{Answer, Solution};
+use crate::{
+    geometry::{Direction, Point},
+    grid::Grid,
+    Result,
+};
 
 pub struct Day12;
 
-type Input = Vec<Vec<char>>;
-type Position = (usize, usize);
-
-mod parser {
-    use nom::IResult;
-
-    use super::Input;
-
-    pub fn parse(input: &str) -> IResult<&str, Input> {
-        nom::sequence::preceded(
-            nom::combinator::opt(nom::character::complete::newline),
-            nom::multi::separated_list1(
-                nom::character::complete::newline,
-                nom::multi::many1(nom::character::complete::satisfy(|ch| ch.is_alphabetic())),
-            ),
-        )(input)
-    }
-}
+type Input = Grid<char>;
 
 impl Day12 {
-    fn parse(&self, input: &str) -> Result<Input, String> {
-        let (_, input) = parser::parse(input).map_err(|err| format!("{:#?}", err))?;
-
-        Ok(input)
-    }
-
-    fn is_out_bound(&self, position: (i32, i32), m: i32, n: i32) -> bool {
-        let (r, c) = position;
-
-        r < 0 || c < 0 || r > m - 1 || c > n - 1
-    }
-
     fn find_region(
         &self,
         map: &Input,
-        visited: &mut HashSet<Position>,
-        position: Position,
-        plant: char,
-    ) -> HashSet<Position> {
-        if map[position.0][position.1] != plant {
-            return HashSet::new();
-        }
-
+        visited: &mut HashSet<Point>,
+        position: Point,
+    ) -> HashSet<Point> {
+        let plant = map[position];
+        let mut region = HashSet::from([position]);
+        let mut stack = vec![position];
         visited.insert(position);
 
-        let m = map.len() as i32;
-        let n = map[0].len() as i32;
-        let row = position.0 as i32;
-        let col = position.1 as i32;
-
-        let mut region = HashSet::new();
-        region.insert(position);
-
-        let directions = [(-1, 0), (1, 0), (0, -1), (0, 1)];
-        directions.iter().for_each(|(dr, dc)| {
-            if !self.is_out_bound((row + dr, col + dc), m, n) {
-                let position = ((row + dr) as usize, (col + dc) as usize);
-                if !visited.contains(&position) {
-                    region.extend(self.find_region(map, visited, position, plant));
+        while let Some(position) = stack.pop() {
+            for next in map.neighbours4(position) {
+                if map[next] == plant && visited.insert(next) {
+                    region.insert(next);
+                    stack.push(next);
                 }
             }
-        });
+        }
 
         region
     }
 
-    fn find_regions(&self, map: &Input) -> Vec<HashSet<Position>> {
+    fn find_regions(&self, map: &Input) -> Vec<HashSet<Point>> {
         let mut regions = vec![];
         let mut visited = HashSet::new();
 
-        map.iter().enumerate().for_each(|(row_idx, rows)| {
-            rows.iter().enumerate().for_each(|(col_idx, &plant)| {
-                let position = (row_idx, col_idx);
-
-                if !visited.contains(&position) {
-                    regions.push(self.find_region(map, &mut visited, position, plant));
-                }
-            })
-        });
+        for position in map.points() {
+            if !visited.contains(&position) {
+                regions.push(self.find_region(map, &mut visited, position));
+            }
+        }
 
         regions
     }
+
+    fn is_same_plant(&self, map: &Input, position: Point, plant: char) -> bool {
+        map.get(position) == Some(&plant)
+    }
 }
 
-impl Puzzle for Day12 {
-    type Output = Result<usize, String>;
+impl Solution for Day12 {
+    const DAY: i32 = 12;
 
-    fn part1(&self, input: &str) -> Self::Output {
-        let map = self.parse(input)?;
-        let regions = self.find_regions(&map);
+    type Input<'a> = Input;
+
+    fn parse(&self, input: &str) -> Result<Input> {
+        Grid::parse(input, |c| c.is_alphabetic().then_some(c))
+    }
+
+    fn solve1(&self, map: &Self::Input<'_>) -> Result<Answer> {
+        let regions = self.find_regions(map);
 
         Ok(regions
             .iter()
             .map(|region| {
                 region
                     .iter()
-                    .map(|pos| {
-                        let m = map.len() as i32;
-                        let n = map[0].len() as i32;
-                        let row = pos.0 as i32;
-                        let col = pos.1 as i32;
-
-                        let directions = [(-1, 0), (1, 0), (0, -1), (0, 1)];
-                        directions
+                    .map(|&pos| {
+                        Direction::ALL
                             .iter()
-                            .filter(|(dr, dc)| {
-                                self.is_out_bound((row + dr, col + dc), m, n)
-                                    || map[(row + dr) as usize][(col + dc) as usize]
-                                        != map[pos.0][pos.1]
-                            })
+                            .filter(|&&side| !self.is_same_plant(map, pos + side, map[pos]))
                             .count()
                     })
                     .sum::<usize>()
                     * region.len()
             })
-            .sum())
+            .sum::<usize>()
+            .into())
     }
 
-    fn part2(&self, input: &str) -> Self::Output {
-        let map = self.parse(input)?;
-        let regions = self.find_regions(&map);
+    fn solve2(&self, map: &Self::Input<'_>) -> Result<Answer> {
+        let regions = self.find_regions(map);
 
+        // Counts corners, which is the same as counting sides.
         Ok(regions
             .iter()
             .map(|region| {
                 region
                     .iter()
-                    .map(|pos| {
-                        let m = map.len() as i32;
-                        let n = map[0].len() as i32;
-                        let row = pos.0 as i32;
-                        let col = pos.1 as i32;
-
-                        let edges = [
-                            [(-1, -1), (-1, 0), (0, -1)],
-                            [(1, -1), (1, 0), (0, -1)],
-                            [(-1, 1), (-1, 0), (0, 1)],
-                            [(1, 1), (1, 0), (0, 1)],
+                    .map(|&pos| {
+                        let plant = map[pos];
+                        let corners = [
+                            (Direction::Up, Direction::Left),
+                            (Direction::Down, Direction::Left),
+                            (Direction::Up, Direction::Right),
+                            (Direction::Down, Direction::Right),
                         ];
 
-                        edges
+                        corners
                             .iter()
-                            .filter(|[edge, side1, side2]| {
-                                let is_edge_out_bound =
-                                    self.is_out_bound((row + edge.0, col + edge.1), m, n);
-                                let is_side1_out_bound =
-                                    self.is_out_bound((row + side1.0, col + side1.1), m, n);
-                                let is_side2_out_bound =
-                                    self.is_out_bound((row + side2.0, col + side2.1), m, n);
-
-                                let plant = map[pos.0][pos.1];
-
-                                if is_side1_out_bound && is_side2_out_bound {
-                                    return true;
-                                }
-
-                                if is_side1_out_bound {
-                                    return map[(row + side2.0) as usize][(col + side2.1) as usize]
-                                        != plant;
-                                } else if is_side2_out_bound {
-                                    return map[(row + side1.0) as usize][(col + side1.1) as usize]
-                                        != plant;
-                                }
-
-                                let edge_plant =
-                                    map[(row + edge.0) as usize][(col + edge.1) as usize];
-
-                                let side1_plant =
-                                    map[(row + side1.0) as usize][(col + side1.1) as usize];
-                                let side2_plant =
-                                    map[(row + side2.0) as usize][(col + side2.1) as usize];
-
-                                if side1_plant != plant && side2_plant != plant {
-                                    true
-                                } else {
-                                    side1_plant == plant
-                                        && side2_plant == plant
-                                        && (is_edge_out_bound || edge_plant != plant)
-                                }
+                            .filter(|&&(side1, side2)| {
+                                let side1_same = self.is_same_plant(map, pos + side1, plant);
+                                let side2_same = self.is_same_plant(map, pos + side2, plant);
+                                let edge_same =
+                                    self.is_same_plant(map, pos + side1 + side2, plant);
+
+                                (!side1_same && !side2_same)
+                                    || (side1_same && side2_same && !edge_same)
                             })
                             .count()
                     })
                     .sum::<usize>()
                     * region.len()
             })
-            .sum())
-    }
-
-    fn solve(&self, input: &str) {
-        let ans1 = self.part1(&input);
-        println!("Answer of Day 12 Part 1: {:#?}", ans1.unwrap());
-
-        let ans2 = self.part2(&input);
-        println!("Answer of Day 12 Part 2: {:#?}", ans2.unwrap());
+            .sum::<usize>()
+            .into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::puzzles::Puzzle;
 
-    const TESTCASE: &'static str = r"
+    const TESTCASE: &str = r"
 RRRRIICCFF
 RRRRIICCCF
 VVRRRCCFFF
@@ -233,36 +155,36 @@ MMMISSJEEE";
             vec!['M', 'M', 'M', 'I', 'S', 'S', 'J', 'E', 'E', 'E'],
         ];
 
-        assert_eq!(puzzle.parse(&TESTCASE).unwrap(), map);
+        assert_eq!(
+            puzzle.parse(TESTCASE).unwrap(),
+            Grid::from_rows(map).unwrap()
+        );
     }
 
     #[test]
     fn test_puzzle_day12_find_regions() {
         let puzzle = Day12;
-        let map = puzzle.parse(&TESTCASE).unwrap();
+        let map = puzzle.parse(TESTCASE).unwrap();
 
         let regions = puzzle.find_regions(&map);
         for region in regions {
             let first = region.iter().next().unwrap();
 
-            assert!(region
-                .iter()
-                .map(|pos| map[pos.0][pos.1])
-                .all(|x| x == map[first.0][first.1]));
+            assert!(region.iter().map(|pos| map[*pos]).all(|x| x == map[*first]));
         }
     }
 
     #[test]
     fn test_puzzle_day12_part1() {
         let puzzle = Day12;
-        assert_eq!(puzzle.part1(&TESTCASE).unwrap(), 1930);
+        assert_eq!(puzzle.part1(TESTCASE).unwrap(), 1930.into());
     }
 
     #[test]
     fn test_puzzle_day12_part2() {
         let puzzle = Day12;
 
-        assert_eq!(puzzle.part2(&TESTCASE).unwrap(), 1206);
+        assert_eq!(puzzle.part2(TESTCASE).unwrap(), 1206.into());
 
         let e_shape_testcase = "
 EEEEE
@@ -271,7 +193,7 @@ EEEEE
 EXXXX
 EEEEE";
 
-        assert_eq!(puzzle.part2(e_shape_testcase).unwrap(), 236);
+        assert_eq!(puzzle.part2(e_shape_testcase).unwrap(), 236.into());
 
         let mobius_testcase = "
 AAAAAA
@@ -280,6 +202,6 @@ AAABBA
 ABBAAA
 ABBAAA
 AAAAAA";
-        assert_eq!(puzzle.part2(mobius_testcase).unwrap(), 368);
+        assert_eq!(puzzle.part2(mobius_testcase).unwrap(), 368.into());
     }
 }
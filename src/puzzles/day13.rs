@@ -1,11 +1,12 @@
-use super::Puzzle;
+use super::{parse_input, Answer, Solution};
+use crate::Result;
 
 pub struct Day13;
 
 type Input = Vec<Machine>;
 
 #[derive(PartialEq, Eq, Debug)]
-struct Machine {
+pub(crate) struct Machine {
     button_a: Button,
     button_b: Button,
     prize: Prize,
@@ -31,7 +32,7 @@ mod parser {
     fn parse_offset<'a>(axis: &'a str) -> impl FnMut(&'a str) -> IResult<&'a str, i64> {
         move |input: &str| {
             nom::sequence::preceded(
-                nom::bytes::complete::tag(format!("{}", axis).as_str()),
+                nom::bytes::complete::tag(axis),
                 nom::character::complete::i64,
             )(input)
         }
@@ -90,20 +91,16 @@ mod parser {
     }
 }
 
-impl Day13 {
-    fn parse(&self, input: &str) -> Result<Input, String> {
-        let (_, input) = parser::parse(input).map_err(|err| format!("{:#?}", err))?;
+impl Solution for Day13 {
+    const DAY: i32 = 13;
 
-        Ok(input)
-    }
-}
+    type Input<'a> = Input;
 
-impl Puzzle for Day13 {
-    type Output = Result<i64, String>;
-
-    fn part1(&self, input: &str) -> Self::Output {
-        let machines = self.parse(input)?;
+    fn parse(&self, input: &str) -> Result<Input> {
+        parse_input(input, parser::parse)
+    }
 
+    fn solve1(&self, machines: &Self::Input<'_>) -> Result<Answer> {
         Ok(machines
             .iter()
             .map(|machine| {
@@ -126,12 +123,11 @@ impl Puzzle for Day13 {
                     0
                 }
             })
-            .sum())
+            .sum::<i64>()
+            .into())
     }
 
-    fn part2(&self, input: &str) -> Self::Output {
-        let machines = self.parse(input)?;
-
+    fn solve2(&self, machines: &Self::Input<'_>) -> Result<Answer> {
         Ok(machines
             .iter()
             .map(|machine| {
@@ -156,23 +152,17 @@ impl Puzzle for Day13 {
                     0
                 }
             })
-            .sum())
-    }
-
-    fn solve(&self, input: &str) {
-        let ans1 = self.part1(&input);
-        println!("Answer of Day 13 Part 1: {:#?}", ans1.unwrap());
-
-        let ans2 = self.part2(&input);
-        println!("Answer of Day 13 Part 2: {:#?}", ans2.unwrap());
+            .sum::<i64>()
+            .into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::puzzles::Puzzle;
 
-    const TESTCASE: &'static str = r"
+    const TESTCASE: &str = r"
 Button A: X+94, Y+34
 Button B: X+22, Y+67
 Prize: X=8400, Y=5400
@@ -215,19 +205,19 @@ Prize: X=18641, Y=10279";
             },
         ];
 
-        assert_eq!(puzzle.parse(&TESTCASE).unwrap(), machines);
+        assert_eq!(puzzle.parse(TESTCASE).unwrap(), machines);
     }
 
     #[test]
     fn test_puzzle_day13_part1() {
         let puzzle = Day13;
-        assert_eq!(puzzle.part1(&TESTCASE).unwrap(), 480);
+        assert_eq!(puzzle.part1(TESTCASE).unwrap(), 480.into());
     }
 
     #[test]
     fn test_puzzle_day13_part2() {
         let puzzle = Day13;
 
-        assert_eq!(puzzle.part2(&TESTCASE).unwrap(), 875318608908);
+        assert_eq!(puzzle.part2(TESTCASE).unwrap(), 875318608908_i64.into());
     }
 }
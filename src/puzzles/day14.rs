@@ -1,4 +1,7 @@
-use super::Puzzle;
+use std::collections::HashSet;
+
+use super::{parse_input, Answer, Solution};
+use crate::{Error, Result};
 
 pub struct Day14;
 
@@ -6,6 +9,7 @@ type Input = Vec<(Position, Velocity)>;
 
 type Position = (i64, i64);
 type Velocity = (i64, i64);
+type MapSize = (i64, i64);
 
 mod parser {
     use nom::IResult;
@@ -50,168 +54,160 @@ mod parser {
     }
 }
 
-impl Day14 {
-    fn parse(&self, input: &str) -> Result<Input, String> {
-        let (_, input) = parser::parse(input).map_err(|err| format!("{:#?}", err))?;
+/// Way to recognise the frame where the robots draw the picture.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Detector {
+    /// The frame with the lowest safety factor, the picture leaves the quadrants unbalanced.
+    SafetyFactor,
+    /// The columns and rows with the lowest variance, combined by the Chinese remainder theorem.
+    Variance,
+    /// The first frame where no two robots share a tile.
+    NoOverlap,
+}
 
-        Ok(input)
-    }
+impl Day14 {
+    const MAP_SIZE: MapSize = (101, 103);
 
-    fn init_map(
-        &self,
-        robots: &Vec<(Position, Velocity)>,
-        map_size: (usize, usize),
-    ) -> (Vec<Vec<usize>>, Vec<(Position, Velocity)>) {
-        let mut map = vec![vec![0; map_size.0]; map_size.1];
-        let robots: Vec<(Position, Velocity)> = robots
+    fn positions_at(&self, robots: &Input, map_size: MapSize, seconds: i64) -> Vec<Position> {
+        robots
             .iter()
-            .map(|(pos, velocity)| {
-                let col = pos.0;
-                let row = pos.1;
-
-                let new_position_col = {
-                    if col < 0 {
-                        map_size.0 as i64 + col
-                    } else if col > map_size.0 as i64 - 1 {
-                        col - map_size.0 as i64
-                    } else {
-                        col
-                    }
-                };
-
-                let new_position_row = {
-                    if row < 0 {
-                        map_size.1 as i64 + row
-                    } else if row > map_size.1 as i64 - 1 {
-                        row - map_size.1 as i64
-                    } else {
-                        row
-                    }
-                };
-
-                ((new_position_col, new_position_row), *velocity)
+            .map(|(position, velocity)| {
+                (
+                    (position.0 + velocity.0 * seconds).rem_euclid(map_size.0),
+                    (position.1 + velocity.1 * seconds).rem_euclid(map_size.1),
+                )
             })
-            .collect();
+            .collect()
+    }
 
-        robots
-            .iter()
-            .map(|(pos, _)| *pos)
-            .for_each(|(col, row)| map[row as usize][col as usize] += 1);
+    /// Number of robots on each tile, indexed by row then column.
+    fn frame(&self, positions: &[Position], map_size: MapSize) -> Vec<Vec<usize>> {
+        let mut map = vec![vec![0; map_size.0 as usize]; map_size.1 as usize];
+        for (col, row) in positions {
+            map[*row as usize][*col as usize] += 1;
+        }
 
-        (map, robots)
+        map
     }
 
-    fn move_robot(
-        &self,
-        map: &mut Vec<Vec<usize>>,
-        position: Position,
-        velocity: Velocity,
-        map_size: (usize, usize),
-        steps: i64,
-    ) -> (Position, Velocity) {
-        let cols = map_size.0 as i64;
-        let rows = map_size.1 as i64;
-
-        let new_position_col = if (position.0 + velocity.0 * steps) % cols < 0 {
-            (position.0 + velocity.0 * steps) % cols + cols
-        } else {
-            (position.0 + velocity.0 * steps) % cols
-        };
+    fn safety_factor(&self, positions: &[Position], map_size: MapSize) -> usize {
+        let col_mid = map_size.0 / 2;
+        let row_mid = map_size.1 / 2;
 
-        let new_position_row = if (position.1 + velocity.1 * steps) % rows < 0 {
-            (position.1 + velocity.1 * steps) % rows + rows
-        } else {
-            (position.1 + velocity.1 * steps) % rows
-        };
+        let mut quadrants = [0; 4];
+        for (col, row) in positions {
+            if *col == col_mid || *row == row_mid {
+                continue;
+            }
 
-        map[position.1 as usize][position.0 as usize] -= 1;
-        map[new_position_row as usize][new_position_col as usize] += 1;
+            quadrants[usize::from(*col > col_mid) + 2 * usize::from(*row > row_mid)] += 1;
+        }
 
-        ((new_position_col, new_position_row), velocity)
+        quadrants.iter().product()
     }
 
-    fn _print_map(&self, map: &Vec<Vec<usize>>, map_size: (usize, usize)) {
-        print!("\x1B[2J\x1B[H");
-
-        for r in 0..map_size.1 {
-            let mut row = String::new();
-            for c in 0..map_size.0 {
-                row += match map[r][c] {
-                    0 => ".",
-                    _ => "x",
-                }
-            }
+    fn variance(&self, values: &[i64]) -> f64 {
+        let count = values.len().max(1) as f64;
+        let mean = values.iter().sum::<i64>() as f64 / count;
 
-            println!("{}", row);
-        }
+        values
+            .iter()
+            .map(|value| (*value as f64 - mean).powi(2))
+            .sum::<f64>()
+            / count
     }
-}
 
-impl Puzzle for Day14 {
-    type Output = Result<usize, String>;
+    /// First second in a full period at which `detector` spots the picture.
+    fn find_picture(&self, robots: &Input, map_size: MapSize, detector: Detector) -> Option<i64> {
+        let period = map_size.0 * map_size.1;
+
+        match detector {
+            Detector::SafetyFactor => (0..period).min_by_key(|seconds| {
+                self.safety_factor(&self.positions_at(robots, map_size, *seconds), map_size)
+            }),
+            Detector::NoOverlap => (0..period).find(|seconds| {
+                let positions = self.positions_at(robots, map_size, *seconds);
+                positions.iter().collect::<HashSet<_>>().len() == positions.len()
+            }),
+            Detector::Variance => {
+                let lowest_variance = |size: i64, axis: fn(&Position) -> i64| {
+                    (0..size).min_by(|a, b| {
+                        let variance = |seconds| {
+                            let positions = self.positions_at(robots, map_size, seconds);
+                            self.variance(&positions.iter().map(axis).collect::<Vec<_>>())
+                        };
+                        variance(*a).total_cmp(&variance(*b))
+                    })
+                };
 
-    fn part1(&self, input: &str) -> Self::Output {
-        let robots = self.parse(input)?;
+                let col_seconds = lowest_variance(map_size.0, |position| position.0)?;
+                let row_seconds = lowest_variance(map_size.1, |position| position.1)?;
 
-        let map_size = if cfg!(test) { (11, 7) } else { (101, 103) };
+                (0..map_size.1)
+                    .map(|k| col_seconds + map_size.0 * k)
+                    .find(|seconds| seconds % map_size.1 == row_seconds)
+            }
+        }
+    }
 
-        let (mut map, mut robots) = self.init_map(&robots, map_size);
+    /// Draws the robots after `seconds`, `x` marking the occupied tiles.
+    pub fn render_frame(&self, input: &str, seconds: i64) -> Result<String> {
+        let robots = self.parse(input)?;
+        let positions = self.positions_at(&robots, Self::MAP_SIZE, seconds);
 
-        robots = robots
+        Ok(self
+            .frame(&positions, Self::MAP_SIZE)
             .iter()
-            .map(|(position, velocity)| {
-                self.move_robot(&mut map, *position, *velocity, map_size, 100)
+            .map(|row| {
+                row.iter()
+                    .map(|count| if *count == 0 { '.' } else { 'x' })
+                    .collect::<String>()
             })
-            .collect();
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
 
-        let col_mid = map_size.0 / 2;
-        let row_mid = map_size.1 / 2;
+    fn count_safety_factor(&self, robots: &Input, map_size: MapSize) -> usize {
+        let positions = self.positions_at(robots, map_size, 100);
 
-        let quadrants = [
-            [(0, col_mid), (0, row_mid)],
-            [(col_mid + 1, map_size.0), (0, row_mid)],
-            [(0, col_mid), (row_mid + 1, map_size.1)],
-            [(col_mid + 1, map_size.0), (row_mid + 1, map_size.1)],
-        ];
+        self.safety_factor(&positions, map_size)
+    }
 
-        Ok(quadrants
-            .map(|[col_range, row_range]| {
-                robots
-                    .iter()
-                    .map(|(pos, _)| pos)
-                    .filter(|&pos| {
-                        (row_range.0..row_range.1).contains(&(pos.1 as usize))
-                            && (col_range.0..col_range.1).contains(&(pos.0 as usize))
-                    })
-                    .count()
-            })
-            .into_iter()
-            .product())
+    fn picture_seconds(&self, robots: &Input, detector: Detector) -> Result<i64> {
+        self.find_picture(robots, Self::MAP_SIZE, detector)
+            .ok_or_else(|| Error::Solve(String::from("the robots never draw a picture")))
     }
 
-    fn part2(&self, input: &str) -> Self::Output {
-        let robots = self.parse(input)?;
-        let map_size = if cfg!(test) { (11, 7) } else { (101, 103) };
-        let (mut _map, mut _robots) = self.init_map(&robots, map_size);
-        let mut _steps = 0;
+    /// Seconds until the robots draw the picture, as seen by `detector`.
+    pub fn seconds_to_picture(&self, input: &str, detector: Detector) -> Result<i64> {
+        self.picture_seconds(&self.parse(input)?, detector)
+    }
+}
+
+impl Solution for Day14 {
+    const DAY: i32 = 14;
 
-        Ok(_steps)
+    type Input<'a> = Input;
+
+    fn parse(&self, input: &str) -> Result<Input> {
+        parse_input(input, parser::parse)
     }
 
-    fn solve(&self, input: &str) {
-        let ans1 = self.part1(&input);
-        println!("Answer of Day 14 Part 1: {:#?}", ans1.unwrap());
+    fn solve1(&self, robots: &Self::Input<'_>) -> Result<Answer> {
+        Ok(self.count_safety_factor(robots, Self::MAP_SIZE).into())
+    }
 
-        let ans2 = self.part2(&input);
-        println!("Answer of Day 14 Part 2: {:#?}", ans2.unwrap());
+    fn solve2(&self, robots: &Self::Input<'_>) -> Result<Answer> {
+        Ok(self.picture_seconds(robots, Detector::Variance)?.into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::puzzles::Puzzle;
 
-    const TESTCASE: &'static str = r"
+    const TESTCASE: &str = r"
 p=0,4 v=3,-3
 p=6,3 v=-1,-3
 p=10,3 v=-1,2
@@ -243,20 +239,91 @@ p=9,5 v=-3,-3";
             ((9, 5), (-3, -3)),
         ];
 
-        assert_eq!(puzzle.parse(&TESTCASE).unwrap(), robots);
+        assert_eq!(puzzle.parse(TESTCASE).unwrap(), robots);
     }
 
     #[test]
     fn test_puzzle_day14_part1() {
         let puzzle = Day14;
 
-        assert_eq!(puzzle.part1(&TESTCASE).unwrap(), 12);
+        assert_eq!(
+            puzzle.count_safety_factor(&puzzle.parse(TESTCASE).unwrap(), (11, 7)),
+            12
+        );
+    }
+
+    /// Robots drawing `picture` after `seconds`, with pseudo-random velocities.
+    fn robots_drawing(picture: &[Position], map_size: MapSize, seconds: i64) -> Input {
+        let mut seed = 42_i64;
+        let mut next = |modulo: i64| {
+            seed = (seed * 1103515245 + 12345) % (1 << 31);
+            (seed >> 16) % modulo
+        };
+
+        picture
+            .iter()
+            .map(|(col, row)| {
+                let velocity = (next(99) + 1, -(next(99) + 1));
+                let start = (
+                    (col - velocity.0 * seconds).rem_euclid(map_size.0),
+                    (row - velocity.1 * seconds).rem_euclid(map_size.1),
+                );
+                (start, velocity)
+            })
+            .collect()
     }
 
     #[test]
     fn test_puzzle_day14_part2() {
         let puzzle = Day14;
+        let size = Day14::MAP_SIZE;
+
+        // A filled rectangle is compact and has no overlapping robots.
+        let rectangle: Vec<Position> = (10..35)
+            .flat_map(|col| (15..35).map(move |row| (col, row)))
+            .collect();
+        let robots = robots_drawing(&rectangle, size, 7000);
+
+        for detector in [Detector::Variance, Detector::NoOverlap] {
+            assert_eq!(
+                puzzle.find_picture(&robots, size, detector),
+                Some(7000),
+                "{:?}",
+                detector
+            );
+        }
+
+        let frame = puzzle.frame(&puzzle.positions_at(&robots, size, 7000), size);
+        assert_eq!(
+            frame[15][9..36].iter().filter(|count| **count == 1).count(),
+            25
+        );
+
+        let input = robots
+            .iter()
+            .map(|((px, py), (vx, vy))| format!("p={},{} v={},{}", px, py, vx, vy))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(puzzle.part2(&input).unwrap(), 7000.into());
+
+        let rendered = puzzle.render_frame(&input, 7000).unwrap();
+        assert_eq!(rendered.lines().nth(15).unwrap().matches('x').count(), 25);
+
+        // A cross on the middle lines leaves a single robot in each quadrant.
+        let cross: Vec<Position> = (0..size.0)
+            .map(|col| (col, size.1 / 2))
+            .chain(
+                (0..size.1)
+                    .filter(|row| *row != size.1 / 2)
+                    .map(|row| (size.0 / 2, row)),
+            )
+            .chain([(10, 10), (90, 10), (10, 90), (90, 90)])
+            .collect();
+        let robots = robots_drawing(&cross, size, 7000);
 
-        assert_eq!(puzzle.part2(&TESTCASE).unwrap(), 0);
+        assert_eq!(
+            puzzle.find_picture(&robots, size, Detector::SafetyFactor),
+            Some(7000)
+        );
     }
 }
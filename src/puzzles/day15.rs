@@ -1,99 +1,74 @@
-use super::Puzzle;
+use std::collections::{HashSet, VecDeque};
+
+use super::{parse_input, Answer, Solution};
+use crate::{
+    geometry::{Direction, Point},
+    grid::Grid,
+    Result,
+};
 
 pub struct Day15;
 
-struct Input {
+pub(crate) struct Input {
     map: Map,
-    movements: Vec<Movement>,
+    movements: Vec<Direction>,
 }
 
+#[derive(Clone)]
 struct Map {
-    robot_position: Position,
-    boxes_position: Vec<Position>,
-    wall_position: Vec<Position>,
-    map_size: (usize, usize),
+    robot_position: Point,
+    tiles: Grid<Tile>,
 }
 
-type Position = (usize, usize);
-enum Movement {
-    Up,
-    Down,
-    Left,
-    Right,
-    Stay,
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Tile {
+    Wall,
+    Box,
+    BoxLeft,
+    BoxRight,
+    Empty,
 }
 
 mod parser {
     use nom::IResult;
 
-    use super::{Input, Map, Movement, Position};
+    use super::{Input, Map, Tile};
+    use crate::{
+        geometry::Direction,
+        grid::{self, Grid},
+    };
 
     fn parse_map(input: &str) -> IResult<&str, Map> {
-        let (input, map) = nom::multi::separated_list1(
-            nom::character::complete::newline,
-            nom::multi::many1(nom::character::complete::satisfy(|ch| ch != '\n')),
-        )(input)?;
-
-        let m = map.len() - 2;
-        let n = map[0].len() - 2;
-
-        let map_size = (m, n);
-        let mut robot_position: Position = (0, 0);
-        let mut wall_position = vec![];
-
-        let boxes_position = map
-            .iter()
-            .enumerate()
-            .flat_map(|(row_idx, rows)| {
-                let boxes = rows
-                    .iter()
-                    .enumerate()
-                    .filter_map(|(col_idx, char)| match char {
-                        'O' => Some((row_idx - 1, col_idx - 1)),
-                        '#' => {
-                            if row_idx > 0 && row_idx < m + 1 && col_idx > 0 && col_idx < n + 1 {
-                                wall_position.push((row_idx - 1, col_idx - 1));
-                            }
-                            None
-                        }
-                        '@' => {
-                            robot_position = (row_idx - 1, col_idx - 1);
-                            None
-                        }
-                        _ => None,
-                    })
-                    .collect::<Vec<(usize, usize)>>();
-                boxes
-            })
-            .collect();
-
-        Ok((
-            input,
-            Map {
-                robot_position,
-                boxes_position,
-                wall_position,
-                map_size,
+        nom::combinator::map_opt(
+            grid::parser(|c| "#O.@".contains(c).then_some(c)),
+            |map: Grid<char>| {
+                Some(Map {
+                    robot_position: map.find(&'@')?,
+                    tiles: map.map(|c| match c {
+                        '#' => Tile::Wall,
+                        'O' => Tile::Box,
+                        _ => Tile::Empty,
+                    }),
+                })
             },
-        ))
+        )(input)
     }
 
-    fn parse_movements(input: &str) -> IResult<&str, Vec<Movement>> {
-        nom::multi::many1(nom::combinator::map_opt(
-            nom::character::complete::anychar,
-            |c| match c {
-                '^' => Some(Movement::Up),
-                '<' => Some(Movement::Left),
-                'v' => Some(Movement::Down),
-                '>' => Some(Movement::Right),
-                '\n' => Some(Movement::Stay),
-                _ => panic!("Invalid movement character"),
-            },
-        ))(input)
+    fn parse_movements(input: &str) -> IResult<&str, Vec<Direction>> {
+        nom::combinator::map(
+            nom::multi::many1(nom::combinator::map_opt(
+                nom::character::complete::anychar,
+                |c| match c {
+                    '\n' => Some(None),
+                    _ => Direction::try_from(c).ok().map(Some),
+                },
+            )),
+            |movements| movements.into_iter().flatten().collect(),
+        )(input)
     }
 
     pub fn parse(input: &str) -> IResult<&str, Input> {
-        nom::combinator::map_res(
+        nom::combinator::map(
             nom::sequence::preceded(
                 nom::multi::many0(nom::character::complete::newline),
                 nom::sequence::separated_pair(
@@ -102,228 +77,129 @@ mod parser {
                     parse_movements,
                 ),
             ),
-            |(map, movements)| Ok::<Input, &str>(Input { map, movements }),
+            |(map, movements)| Input { map, movements },
         )(input)
     }
 }
 
 impl Day15 {
-    fn parse(&self, input: &str) -> Result<Input, String> {
-        let (_, input) = parser::parse(input).map_err(|err| format!("{:#?}", err))?;
+    /// Doubles the width of the warehouse, every box becoming a `[]` pair.
+    fn widen(&self, map: &Map) -> Map {
+        let mut tiles = Grid::new(map.tiles.width() * 2, map.tiles.height(), Tile::Empty);
+        for (pos, tile) in map.tiles.iter() {
+            let (left, right) = match tile {
+                Tile::Box => (Tile::BoxLeft, Tile::BoxRight),
+                tile => (*tile, *tile),
+            };
+            tiles[Point::new(pos.x * 2, pos.y)] = left;
+            tiles[Point::new(pos.x * 2 + 1, pos.y)] = right;
+        }
 
-        Ok(input)
+        Map {
+            robot_position: Point::new(map.robot_position.x * 2, map.robot_position.y),
+            tiles,
+        }
     }
 
-    fn is_out_bound(&self, position: (i32, i32), map_size: (usize, usize)) -> bool {
-        let (row, col) = position;
-        let m = map_size.0 as i32;
-        let n = map_size.1 as i32;
+    /// Cells of the boxes pushed when the robot moves towards `direction`, in the order they are
+    /// reached, or `None` if a wall or the edge of the map blocks any of them.
+    fn pushed_cells(&self, map: &Map, direction: Direction) -> Option<Vec<Point>> {
+        let mut cells = vec![];
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::from([map.robot_position + direction]);
+
+        while let Some(pos) = queue.pop_front() {
+            let partner = match map.tiles.get(pos).copied().unwrap_or(Tile::Wall) {
+                Tile::Wall => return None,
+                Tile::Empty => continue,
+                Tile::Box => None,
+                Tile::BoxLeft => Some(pos + Direction::Right),
+                Tile::BoxRight => Some(pos + Direction::Left),
+            };
+
+            for cell in std::iter::once(pos).chain(partner) {
+                if seen.insert(cell) {
+                    cells.push(cell);
+                    queue.push_back(cell + direction);
+                }
+            }
+        }
 
-        row < 0 || row >= m || col < 0 || col >= n
+        Some(cells)
     }
 
-    fn move_unit(
-        &self,
-        curr_position: &Position,
-        movement: &Movement,
-        boxes_position: &Vec<Position>,
-        wall_position: &Vec<Position>,
-        map_size: (usize, usize),
-    ) -> (Position, Vec<Position>) {
-        let curr_row = curr_position.0 as i32;
-        let curr_col = curr_position.1 as i32;
-
-        let (dr, dc) = match movement {
-            Movement::Up => (-1, 0),
-            Movement::Down => (1, 0),
-            Movement::Left => (0, -1),
-            Movement::Right => (0, 1),
-            Movement::Stay => return (*curr_position, boxes_position.clone()),
+    /// Moves the robot one step, pushing every box it touches at once if there is room.
+    fn move_robot(&self, map: &mut Map, direction: Direction) {
+        let Some(cells) = self.pushed_cells(map, direction) else {
+            return;
         };
 
-        if self.is_out_bound((curr_row + dr, curr_col + dc), map_size)
-            || wall_position.contains(&((curr_row + dr) as usize, (curr_col + dc) as usize))
-        {
-            return (*curr_position, boxes_position.clone());
+        let moved: Vec<(Point, Tile)> = cells
+            .iter()
+            .filter_map(|pos| Some((*pos, *map.tiles.get(*pos)?)))
+            .collect();
+        for (pos, _) in &moved {
+            if let Some(cell) = map.tiles.get_mut(*pos) {
+                *cell = Tile::Empty;
+            }
         }
-
-        let next_row = (curr_row + dr) as usize;
-        let next_col = (curr_col + dc) as usize;
-
-        if !boxes_position.contains(&(next_row, next_col)) {
-            return (
-                (next_row, next_col),
-                // Move box
-                boxes_position
-                    .iter()
-                    .map(|box_position| {
-                        if box_position == curr_position {
-                            (next_row, next_col)
-                        } else {
-                            *box_position
-                        }
-                    })
-                    .collect(),
-            );
+        for (pos, tile) in moved {
+            if let Some(cell) = map.tiles.get_mut(pos + direction) {
+                *cell = tile;
+            }
         }
 
-        let (next_pos, boxes_position) = self.move_unit(
-            &(next_row, next_col),
-            movement,
-            boxes_position,
-            wall_position,
-            map_size,
-        );
-
-        // cannot move
-        if next_pos == (next_row, next_col) {
-            (*curr_position, boxes_position.clone())
-        } else {
-            (
-                (next_row, next_col),
-                boxes_position
-                    .iter()
-                    .map(|box_position| {
-                        if box_position == curr_position {
-                            (next_row, next_col)
-                        } else {
-                            *box_position
-                        }
-                    })
-                    .collect(),
-            )
-        }
+        map.robot_position += direction;
     }
 
-    fn _print_map(
-        &self,
-        robot_position: &Position,
-        boxes_position: &Vec<Position>,
-        wall_position: &Vec<Position>,
-        map_size: (usize, usize),
-    ) {
-        let (m, n) = map_size;
-
-        for _ in 0..n + 2 {
-            print!("#");
-        }
-        println!();
-
-        for i in 0..m {
-            print!("#");
-
-            for j in 0..n {
-                if (i, j) == *robot_position {
-                    print!("@")
-                } else if boxes_position.contains(&(i, j)) {
-                    print!("O")
-                } else if wall_position.contains(&(i, j)) {
-                    print!("#")
-                } else {
-                    print!(".")
-                }
-            }
-
-            print!("#\n");
-        }
-
-        for _ in 0..n + 2 {
-            print!("#");
-        }
-        println!();
-        println!();
+    /// Sum of the GPS coordinates of the boxes, measured from their left edge.
+    fn gps_sum(&self, map: &Map) -> i64 {
+        map.tiles
+            .iter()
+            .filter(|(_, tile)| matches!(tile, Tile::Box | Tile::BoxLeft))
+            .map(|(pos, _)| pos.y * 100 + pos.x)
+            .sum()
     }
 }
 
-impl Puzzle for Day15 {
-    type Output = Result<usize, String>;
-
-    fn part1(&self, input: &str) -> Self::Output {
-        let Input { map, movements } = self.parse(input)?;
-        let Map {
-            mut robot_position,
-            mut boxes_position,
-            wall_position,
-            map_size,
-        } = map;
-
-        movements.iter().for_each(|movement| {
-            (robot_position, boxes_position) = self.move_unit(
-                &robot_position,
-                movement,
-                &boxes_position,
-                &wall_position,
-                map_size,
-            );
-            // self._print_map(&robot_position, &boxes_position, &wall_position, map_size);
-        });
-
-        // self._print_map(&robot_position, &boxes_position, &wall_position, map_size);
-
-        Ok(boxes_position
-            .iter()
-            .map(|(row, col)| (row + 1) * 100 + (col + 1))
-            .sum())
+impl Solution for Day15 {
+    const DAY: i32 = 15;
+
+    type Input<'a> = Input;
+
+    fn parse(&self, input: &str) -> Result<Input> {
+        parse_input(input, parser::parse)
     }
 
-    fn part2(&self, input: &str) -> Self::Output {
-        let _input = self.parse(input)?;
+    fn solve1(&self, input: &Self::Input<'_>) -> Result<Answer> {
+        let Input { map, movements } = input;
+        let mut map = map.clone();
 
-        let Input { map, movements } = self.parse(input)?;
-        let Map {
-            robot_position,
-            boxes_position,
-            wall_position,
-            map_size,
-        } = map;
+        for movement in movements {
+            self.move_robot(&mut map, *movement);
+        }
 
-        let mut scaled_robot_position = (robot_position.0, robot_position.1 * 2);
-        let mut scaled_boxes_position = boxes_position
-            .iter()
-            .flat_map(|(row, col)| [(*row, col * 2), (*row, col * 2 + 1)])
-            .collect();
-        let scaled_wall_position = wall_position
-            .iter()
-            .flat_map(|(row, col)| [(*row, col * 2), (*row, col * 2 + 1)])
-            .collect();
-        let scaled_map_size = (map_size.0, map_size.1 * 2);
-
-        movements.iter().for_each(|movement| {
-            (scaled_robot_position, scaled_boxes_position) = self.move_unit(
-                &scaled_robot_position,
-                movement,
-                &scaled_boxes_position,
-                &scaled_wall_position,
-                scaled_map_size,
-            );
-            self._print_map(
-                &scaled_robot_position,
-                &scaled_boxes_position,
-                &scaled_wall_position,
-                scaled_map_size,
-            );
-        });
-
-        Ok(scaled_boxes_position
-            .iter()
-            .map(|(row, col)| (row + 1) * 100 + (col + 1))
-            .sum())
+        Ok(self.gps_sum(&map).into())
     }
 
-    fn solve(&self, input: &str) {
-        let ans1 = self.part1(&input);
-        println!("Answer of Day 15 Part 1: {:#?}", ans1.unwrap());
+    fn solve2(&self, input: &Self::Input<'_>) -> Result<Answer> {
+        let Input { map, movements } = input;
+        let mut map = self.widen(map);
+
+        for movement in movements {
+            self.move_robot(&mut map, *movement);
+        }
 
-        let ans2 = self.part2(&input);
-        println!("Answer of Day 15 Part 2: {:#?}", ans2.unwrap());
+        Ok(self.gps_sum(&map).into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::puzzles::Puzzle;
 
-    const TESTCASE: &'static str = r"
+    const TESTCASE: &str = r"
 ########
 #..O.O.#
 ##@.O..#
@@ -336,7 +212,7 @@ mod tests {
 <^^>>>vv
 <v>>v<<";
 
-    const LARGER_TESTCASE: &'static str = r"
+    const LARGER_TESTCASE: &str = r"
 ##########
 #..O..O.O#
 #......O.#
@@ -359,7 +235,7 @@ vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
 ^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
 v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
 
-    const PART2_TESTCASE: &'static str = r"#######
+    const PART2_TESTCASE: &str = r"#######
 #...#.#
 #.....#
 #..OO@#
@@ -372,32 +248,57 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
     #[test]
     fn test_puzzle_day15_parse() {
         let puzzle = Day15;
-        let input = puzzle.parse(&TESTCASE).unwrap();
-
-        let robot_position = (1, 1);
-        let boxes_position = vec![(0, 2), (0, 4), (1, 3), (2, 3), (3, 3), (4, 3)];
-        let wall_position = vec![(1, 0), (3, 1)];
-        let map_size = (6, 6);
-
-        assert_eq!(input.map.robot_position, robot_position);
-        assert_eq!(input.map.boxes_position, boxes_position);
-        assert_eq!(input.map.wall_position, wall_position);
-        assert_eq!(input.map.map_size, map_size);
+        let input = puzzle.parse(TESTCASE).unwrap();
+
+        let boxes_position = vec![
+            Point::new(3, 1),
+            Point::new(5, 1),
+            Point::new(4, 2),
+            Point::new(4, 3),
+            Point::new(4, 4),
+            Point::new(4, 5),
+        ];
+        let inner_walls = vec![Point::new(1, 2), Point::new(2, 4)];
+
+        assert_eq!(input.map.robot_position, Point::new(2, 2));
+        assert_eq!(
+            input.map.tiles.find_all(&Tile::Box).collect::<Vec<_>>(),
+            boxes_position
+        );
+        assert_eq!(
+            input
+                .map
+                .tiles
+                .find_all(&Tile::Wall)
+                .filter(|pos| (1..7).contains(&pos.x) && (1..7).contains(&pos.y))
+                .collect::<Vec<_>>(),
+            inner_walls
+        );
+        assert_eq!((input.map.tiles.width(), input.map.tiles.height()), (8, 8));
     }
 
     #[test]
     fn test_puzzle_day15_part1() {
         let puzzle = Day15;
 
-        assert_eq!(puzzle.part1(&TESTCASE).unwrap(), 2028);
-        assert_eq!(puzzle.part1(&LARGER_TESTCASE).unwrap(), 10092);
+        assert_eq!(puzzle.part1(TESTCASE).unwrap(), 2028.into());
+        assert_eq!(puzzle.part1(LARGER_TESTCASE).unwrap(), 10092.into());
     }
 
     #[test]
     fn test_puzzle_day15_part2() {
         let puzzle = Day15;
 
-        assert_eq!(puzzle.part2(&PART2_TESTCASE).unwrap(), 105);
-        assert_eq!(puzzle.part2(&LARGER_TESTCASE).unwrap(), 9021);
+        assert_eq!(puzzle.part2(PART2_TESTCASE).unwrap(), 618.into());
+        assert_eq!(puzzle.part2(LARGER_TESTCASE).unwrap(), 9021.into());
+    }
+
+    #[test]
+    fn test_puzzle_day15_unwalled_map() {
+        let puzzle = Day15;
+        let unwalled = "@O.\n\n<>>>^";
+
+        assert_eq!(puzzle.part1(unwalled).unwrap(), 2.into());
+        assert_eq!(puzzle.part2(unwalled).unwrap(), 4.into());
     }
 }
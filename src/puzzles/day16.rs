@@ -1,97 +1,48 @@
-use std::{
-    collections::{HashSet, VecDeque},
-    i32,
+use std::collections::HashSet;
+
+use super::{parse_input, Answer, Solution};
+use crate::{
+    geometry::{Direction, Point},
+    grid::Grid,
+    pathfinding::{self, Cost},
+    Error, Result,
 };
 
-use super::Puzzle;
-
 pub struct Day16;
 
-struct Input {
+pub(crate) struct Input {
     map: Map,
-    start_position: Position,
-    end_position: Position,
-}
-type Map = Vec<Vec<i32>>;
-type Position = (usize, usize);
-
-#[derive(Clone, Copy, Debug)]
-enum Movement {
-    Up,
-    Down,
-    Left,
-    Right,
-    Unknown,
+    start_position: Point,
+    end_position: Point,
 }
+type Map = Grid<Tile>;
 
-impl Movement {
-    fn rotate(&self) -> [Movement; 2] {
-        match self {
-            Movement::Up => [Movement::Left, Movement::Right],
-            Movement::Down => [Movement::Left, Movement::Right],
-            Movement::Left => [Movement::Up, Movement::Down],
-            Movement::Right => [Movement::Up, Movement::Down],
-            Movement::Unknown => panic!("Error rotate unknown movement"),
-        }
-    }
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Tile {
+    Wall,
+    Empty,
 }
 
 mod parser {
     use nom::IResult;
 
-    use super::{Input, Map, Position};
-
-    fn parse_map(input: &str) -> IResult<&str, (Map, Position, Position)> {
-        let (input, map) = nom::multi::separated_list1(
-            nom::character::complete::newline,
-            nom::multi::many1(nom::character::complete::satisfy(|ch| ch != '\n')),
-        )(input)?;
-
-        let m = map.len() - 2;
-        let n = map[0].len() - 2;
-
-        let mut start_position: Position = (0, 0);
-        let mut end_position: Position = (0, 0);
-
-        let map: Map = map
-            .iter()
-            .enumerate()
-            .filter(|(row_idx, _)| *row_idx > 0 && *row_idx < m + 1)
-            .map(|(row_idx, rows)| {
-                rows.iter()
-                    .enumerate()
-                    .filter(|(col_idx, _)| *col_idx > 0 && *col_idx < n + 1)
-                    .map(|(col_idx, char)| match char {
-                        '.' => 0,
-                        '#' => 1,
-                        'S' => {
-                            start_position = (row_idx - 1, col_idx - 1);
-                            0
-                        }
-                        'E' => {
-                            end_position = (row_idx - 1, col_idx - 1);
-                            0
-                        }
-                        _ => panic!("Invalid character."),
-                    })
-                    .collect::<Vec<i32>>()
-            })
-            .collect();
-
-        Ok((input, (map, start_position, end_position)))
-    }
+    use super::{Input, Tile};
+    use crate::grid::{self, Grid};
 
     pub fn parse(input: &str) -> IResult<&str, Input> {
-        nom::combinator::map_res(
+        nom::combinator::map_opt(
             nom::sequence::preceded(
                 nom::multi::many0(nom::character::complete::newline),
-                parse_map,
+                grid::parser(|c| ".#SE".contains(c).then_some(c)),
             ),
-            |(map, start_position, end_position)| {
-                Ok::<Input, &str>(Input {
-                    map,
-                    start_position,
-                    end_position,
+            |map: Grid<char>| {
+                Some(Input {
+                    start_position: map.find(&'S')?,
+                    end_position: map.find(&'E')?,
+                    map: map.map(|c| match c {
+                        '#' => Tile::Wall,
+                        _ => Tile::Empty,
+                    }),
                 })
             },
         )(input)
@@ -99,236 +50,152 @@ mod parser {
 }
 
 impl Day16 {
-    fn parse(&self, input: &str) -> Result<Input, String> {
-        let (_, input) = parser::parse(input).map_err(|err| format!("{:#?}", err))?;
-
-        Ok(input)
+    fn is_open(&self, map: &Map, position: Point) -> bool {
+        map.get(position) == Some(&Tile::Empty)
     }
 
-    fn is_out_bound(&self, position: (i32, i32), map_size: (usize, usize)) -> bool {
-        let (row, col) = position;
-        let m = map_size.0 as i32;
-        let n = map_size.1 as i32;
+    /// Cheapest cost from the start, facing east, to the end tile.
+    fn min_cost(&self, map: &Map, start_position: &Point, end_position: &Point) -> Result<Cost> {
+        let (_, cost) = pathfinding::dijkstra(
+            (*start_position, Direction::Right),
+            |&(position, direction)| self.moves(map, position, direction),
+            |(position, _)| position == end_position,
+        )
+        .ok_or_else(|| Error::Solve(String::from("the end tile cannot be reached")))?;
 
-        row < 0 || row >= m || col < 0 || col >= n
+        Ok(cost)
     }
 
-    fn generate_cost_map(&self, map: &Map, start_position: &Position) -> Vec<Vec<(Movement, i32)>> {
-        let m = map.len();
-        let n = map[0].len();
-
-        let mut cost_map = vec![vec![(Movement::Unknown, i32::MAX); n]; m];
-        let mut queue = VecDeque::new();
-        queue.push_back((Movement::Left, *start_position, 0));
-
-        while let Some((curr_movement, curr_pos, curr_cost)) = queue.pop_front() {
-            if cost_map[curr_pos.0][curr_pos.1].1 > curr_cost {
-                cost_map[curr_pos.0][curr_pos.1] = (curr_movement, curr_cost)
-            } else {
-                continue;
-            }
-
-            let curr_row = curr_pos.0 as i32;
-            let curr_col = curr_pos.1 as i32;
-
-            for (next_movement, next_cost) in [
-                (curr_movement, 1),
-                (curr_movement.rotate()[0], 1001),
-                (curr_movement.rotate()[1], 1001),
-            ] {
-                let (dr, dc) = match next_movement {
-                    Movement::Up => (-1, 0),
-                    Movement::Down => (1, 0),
-                    Movement::Left => (0, -1),
-                    Movement::Right => (0, 1),
-                    Movement::Unknown => panic!("Error unknown next movement"),
-                };
-
-                if self.is_out_bound((curr_row + dr, curr_col + dc), (m, n))
-                    || map[(curr_row + dr) as usize][(curr_col + dc) as usize] == 1
-                {
-                    continue;
-                }
-
-                let next_row = (curr_row + dr) as usize;
-                let next_col = (curr_col + dc) as usize;
-
-                queue.push_back((next_movement, (next_row, next_col), curr_cost + next_cost));
-            }
-        }
-
-        cost_map
+    /// Moving forward costs 1 and turning in place costs 1000.
+    fn moves(
+        &self,
+        map: &Map,
+        position: Point,
+        direction: Direction,
+    ) -> impl Iterator<Item = ((Point, Direction), Cost)> {
+        let forward = position + direction;
+        let forward = self
+            .is_open(map, forward)
+            .then_some(((forward, direction), 1));
+
+        forward.into_iter().chain([
+            ((position, direction.turn_left()), 1000),
+            ((position, direction.turn_right()), 1000),
+        ])
     }
 
-    fn get_all_min_cost_paths(
+    /// Tiles lying on at least one cheapest path, found by searching from both ends.
+    fn optimal_tiles(
         &self,
         map: &Map,
-        min_cost: i32,
-        start_position: &Position,
-        end_position: &Position,
-    ) -> Vec<HashSet<Position>> {
-        let mut paths = Vec::new();
-        let mut queue = VecDeque::new();
-        queue.push_back((HashSet::new(), *start_position, Movement::Left, 0));
-
-        let m = map.len();
-        let n = map[0].len();
-
-        while let Some((curr_path, curr_pos, curr_movement, curr_cost)) = queue.pop_front() {
-            if curr_path.contains(&curr_pos) {
-                continue;
-            }
-
-            let mut curr_path = curr_path.clone();
-            curr_path.insert(curr_pos);
-
-            if curr_cost > min_cost {
-                continue;
-            }
-
-            if curr_pos == *end_position {
-                if curr_cost == min_cost {
-                    paths.push(curr_path);
-                }
-                continue;
-            }
-            let curr_row = curr_pos.0 as i32;
-            let curr_col = curr_pos.1 as i32;
-
-            for (next_movement, next_cost) in [
-                (curr_movement, 1),
-                (curr_movement.rotate()[0], 1001),
-                (curr_movement.rotate()[1], 1001),
-            ] {
-                let (dr, dc) = match next_movement {
-                    Movement::Up => (-1, 0),
-                    Movement::Down => (1, 0),
-                    Movement::Left => (0, -1),
-                    Movement::Right => (0, 1),
-                    Movement::Unknown => panic!("Error unknown next movement"),
-                };
-
-                if self.is_out_bound((curr_row + dr, curr_col + dc), (m, n))
-                    || map[(curr_row + dr) as usize][(curr_col + dc) as usize] == 1
-                {
-                    continue;
-                }
-
-                let next_row = (curr_row + dr) as usize;
-                let next_col = (curr_col + dc) as usize;
-
-                if curr_path.contains(&(next_row, next_col)) {
-                    continue;
-                }
-
-                queue.push_back((
-                    curr_path.clone(),
-                    (next_row, next_col),
-                    next_movement,
-                    curr_cost + next_cost,
-                ));
-            }
-        }
+        start_position: &Point,
+        end_position: &Point,
+    ) -> Result<HashSet<Point>> {
+        let from_start = pathfinding::dijkstra_all(
+            &[(*start_position, Direction::Right)],
+            |&(position, direction)| self.moves(map, position, direction),
+        );
+
+        let min_cost = Direction::ALL
+            .iter()
+            .filter_map(|direction| from_start.get(&(*end_position, *direction)))
+            .min()
+            .copied()
+            .ok_or_else(|| Error::Solve(String::from("the end tile cannot be reached")))?;
+
+        let ends = Direction::ALL.map(|direction| (*end_position, direction));
+        let to_end = pathfinding::dijkstra_all(&ends, |&(position, direction)| {
+            self.reverse_moves(map, position, direction)
+        });
 
-        paths
+        Ok(from_start
+            .iter()
+            .filter(|(state, cost)| {
+                to_end
+                    .get(state)
+                    .is_some_and(|remaining| *cost + remaining == min_cost)
+            })
+            .map(|((position, _), _)| *position)
+            .collect())
     }
 
-    fn _print_map(
+    /// The moves that lead to the state, used to search backwards from the end.
+    fn reverse_moves(
         &self,
-        map: &Vec<Vec<i32>>,
-        start_position: &Position,
-        end_position: &Position,
-        path: &HashSet<Position>,
-    ) {
-        let m = map.len();
-        let n = map[0].len();
-
-        for _ in 0..n + 2 {
-            print!("#");
-        }
-        println!();
-
-        for i in 0..m {
-            print!("#");
-
-            for j in 0..n {
-                if (i, j) == *start_position {
-                    print!("S")
-                } else if (i, j) == *end_position {
-                    print!("E")
-                } else if map[i][j] == 1 {
-                    print!("#")
-                } else if path.contains(&(i, j)) {
-                    print!("x")
-                } else {
-                    print!(".")
-                }
-            }
-
-            print!("#\n");
-        }
-
-        for _ in 0..n + 2 {
-            print!("#");
-        }
-        println!();
-        println!();
+        map: &Map,
+        position: Point,
+        direction: Direction,
+    ) -> impl Iterator<Item = ((Point, Direction), Cost)> {
+        let backward = position - direction.vector();
+        let backward = self
+            .is_open(map, backward)
+            .then_some(((backward, direction), 1));
+
+        backward.into_iter().chain([
+            ((position, direction.turn_left()), 1000),
+            ((position, direction.turn_right()), 1000),
+        ])
     }
-}
 
-impl Puzzle for Day16 {
-    type Output = Result<i32, String>;
-
-    fn part1(&self, input: &str) -> Self::Output {
+    /// Draws the maze with every tile of a cheapest path marked as `O`.
+    pub fn render_optimal_tiles(&self, input: &str) -> Result<String> {
         let Input {
             map,
             start_position,
             end_position,
         } = self.parse(input)?;
 
-        let cost_map = self.generate_cost_map(&map, &start_position);
+        let tiles = self.optimal_tiles(&map, &start_position, &end_position)?;
+        let mut rendered = map.map(|tile| match tile {
+            Tile::Wall => '#',
+            Tile::Empty => '.',
+        });
+        for position in tiles {
+            rendered[position] = 'O';
+        }
+
+        Ok(rendered.to_string())
+    }
+}
 
-        let min_cost = cost_map[end_position.0][end_position.1].1;
+impl Solution for Day16 {
+    const DAY: i32 = 16;
 
-        Ok(min_cost)
+    type Input<'a> = Input;
+
+    fn parse(&self, input: &str) -> Result<Input> {
+        parse_input(input, parser::parse)
     }
 
-    fn part2(&self, input: &str) -> Self::Output {
+    fn solve1(&self, input: &Self::Input<'_>) -> Result<Answer> {
         let Input {
             map,
             start_position,
             end_position,
-        } = self.parse(input)?;
-
-        let cost_map = self.generate_cost_map(&map, &start_position);
-        let min_cost = cost_map[end_position.0][end_position.1].1;
-        let min_paths = self.get_all_min_cost_paths(&map, min_cost, &start_position, &end_position);
+        } = input;
 
-        println!("{:?}", min_paths);
-
-        Ok(min_paths
-            .iter()
-            .fold(HashSet::new(), |mut all_paths: HashSet<Position>, path| {
-                all_paths.extend(path);
-                all_paths
-            })
-            .len() as i32)
+        Ok(self.min_cost(map, start_position, end_position)?.into())
     }
 
-    fn solve(&self, input: &str) {
-        let ans1 = self.part1(&input);
-        println!("Answer of Day 16 Part 1: {:#?}", ans1.unwrap());
+    fn solve2(&self, input: &Self::Input<'_>) -> Result<Answer> {
+        let Input {
+            map,
+            start_position,
+            end_position,
+        } = input;
 
-        let ans2 = self.part2(&input);
-        println!("Answer of Day 16 Part 2: {:#?}", ans2.unwrap());
+        let tiles = self.optimal_tiles(map, start_position, end_position)?;
+
+        Ok(tiles.len().into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::puzzles::Puzzle;
 
-    const TESTCASE_1: &'static str = r"
+    const TESTCASE_1: &str = r"
 ###############
 #.......#....E#
 #.#.###.#.###.#
@@ -345,7 +212,7 @@ mod tests {
 #S..#.....#...#
 ###############";
 
-    const TESTCASE_2: &'static str = r"
+    const TESTCASE_2: &str = r"
 #################
 #...#...#...#..E#
 #.#.#.#.#.#.#.#.#
@@ -367,45 +234,57 @@ mod tests {
     #[test]
     fn test_puzzle_day16_parse() {
         let puzzle = Day16;
-        let map_1 = vec![
-            vec![0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0],
-            vec![0, 1, 0, 1, 1, 1, 0, 1, 0, 1, 1, 1, 0],
-            vec![0, 0, 0, 0, 0, 1, 0, 1, 0, 0, 0, 1, 0],
-            vec![0, 1, 1, 1, 0, 1, 1, 1, 1, 1, 0, 1, 0],
-            vec![0, 1, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1, 0],
-            vec![0, 1, 0, 1, 1, 1, 1, 1, 0, 1, 1, 1, 0],
-            vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0],
-            vec![1, 1, 0, 1, 0, 1, 1, 1, 1, 1, 0, 1, 0],
-            vec![0, 0, 0, 1, 0, 0, 0, 0, 0, 1, 0, 1, 0],
-            vec![0, 1, 0, 1, 0, 1, 1, 1, 0, 1, 0, 1, 0],
-            vec![0, 0, 0, 0, 0, 1, 0, 0, 0, 1, 0, 1, 0],
-            vec![0, 1, 1, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0],
-            vec![0, 0, 0, 1, 0, 0, 0, 0, 0, 1, 0, 0, 0],
-        ];
-
-        let input_1 = puzzle.parse(&TESTCASE_1).unwrap();
-        assert_eq!(input_1.map, map_1);
-        assert_eq!(input_1.start_position, (12, 0));
-        assert_eq!(input_1.end_position, (0, 12));
-
-        let input_2 = puzzle.parse(&TESTCASE_2).unwrap();
-        assert_eq!(input_2.start_position, (14, 0));
-        assert_eq!(input_2.end_position, (0, 14));
+        let walls_1 = ["###############", "#.......#....E#", "#.#.###.#.###.#"];
+
+        let input_1 = puzzle.parse(TESTCASE_1).unwrap();
+        assert_eq!((input_1.map.width(), input_1.map.height()), (15, 15));
+        for (y, row) in walls_1.iter().enumerate() {
+            let walls: Vec<Tile> = row
+                .chars()
+                .map(|c| if c == '#' { Tile::Wall } else { Tile::Empty })
+                .collect();
+            assert_eq!(
+                input_1.map.row(y).unwrap().copied().collect::<Vec<_>>(),
+                walls
+            );
+        }
+        assert_eq!(input_1.start_position, Point::new(1, 13));
+        assert_eq!(input_1.end_position, Point::new(13, 1));
+
+        let input_2 = puzzle.parse(TESTCASE_2).unwrap();
+        assert_eq!(input_2.start_position, Point::new(1, 15));
+        assert_eq!(input_2.end_position, Point::new(15, 1));
     }
 
     #[test]
     fn test_puzzle_day16_part1() {
         let puzzle = Day16;
 
-        assert_eq!(puzzle.part1(&TESTCASE_1).unwrap(), 7036);
-        assert_eq!(puzzle.part1(&TESTCASE_2).unwrap(), 11048);
+        assert_eq!(puzzle.part1(TESTCASE_1).unwrap(), 7036.into());
+        assert_eq!(puzzle.part1(TESTCASE_2).unwrap(), 11048.into());
     }
 
     #[test]
     fn test_puzzle_day16_part2() {
         let puzzle = Day16;
 
-        assert_eq!(puzzle.part2(&TESTCASE_1).unwrap(), 45);
-        assert_eq!(puzzle.part2(&TESTCASE_2).unwrap(), 64);
+        assert_eq!(puzzle.part2(TESTCASE_1).unwrap(), 45.into());
+        assert_eq!(puzzle.part2(TESTCASE_2).unwrap(), 64.into());
+    }
+
+    #[test]
+    fn test_puzzle_day16_optimal_tiles() {
+        let puzzle = Day16;
+        let rendered = puzzle.render_optimal_tiles(TESTCASE_1).unwrap();
+
+        assert_eq!(rendered.matches('O').count(), 45);
+        assert_eq!(rendered.lines().nth(1), Some("#.......#....O#"));
+        assert_eq!(rendered.lines().nth(13), Some("#O..#.....#OOO#"));
+
+        let walled = "
+#####
+#S#E#
+#####";
+        assert!(puzzle.part2(walled).is_err());
     }
 }
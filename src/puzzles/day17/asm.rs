@@ -0,0 +1,243 @@
+use std::{collections::HashMap, fmt};
+
+use super::{Instruction, Operand};
+use crate::{error::ParseError, Result};
+
+/// Program assembled from mnemonics, with the initial value of the registers.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Assembly {
+    pub registers: [u64; 3],
+    pub code: Vec<u64>,
+}
+
+/// Prints the registers and program the way the puzzle input does.
+impl fmt::Display for Assembly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, value) in ["A", "B", "C"].iter().zip(self.registers) {
+            writeln!(f, "Register {}: {}", name, value)?;
+        }
+
+        let code: Vec<String> = self.code.iter().map(|value| value.to_string()).collect();
+        write!(f, "\nProgram: {}", code.join(","))
+    }
+}
+
+/// Words of `line` with their byte offset in `source`, comments after `;` left out.
+fn words<'a>(source: &str, line: &'a str) -> Vec<(usize, &'a str)> {
+    let line = &line[..line.find(';').unwrap_or(line.len())];
+
+    line.split_whitespace()
+        .map(|word| (word.as_ptr() as usize - source.as_ptr() as usize, word))
+        .collect()
+}
+
+fn number(source: &str, (offset, word): (usize, &str), max: u64) -> Result<u64> {
+    match word.parse() {
+        Ok(value) if value <= max => Ok(value),
+        _ => Err(ParseError::at(
+            source,
+            offset,
+            format!("expected a number up to {}, found `{}`", max, word),
+        )
+        .into()),
+    }
+}
+
+/// Assembles one instruction per line, such as `adv 3`, `out a` or `jnz loop`.
+///
+/// A line may start with labels (`loop:`) naming the address of its instruction, or with a
+/// bare address (`4:`) which is ignored so disassembled listings assemble back. Combo operands
+/// are `0` to `3` or a register, literals are `0` to `7` and `bxc` takes none. The `.a`, `.b`
+/// and `.c` directives set the initial registers, and `;` starts a comment.
+pub fn assemble(source: &str) -> Result<Assembly> {
+    let mut registers = [0; 3];
+    let mut code = vec![];
+    let mut labels = HashMap::new();
+    let mut jumps = vec![];
+
+    for line in source.lines() {
+        let mut words = words(source, line).into_iter().peekable();
+
+        while let Some((offset, label)) = words.next_if(|(_, word)| word.ends_with(':')) {
+            let label = label.trim_end_matches(':');
+            if label.chars().all(|c| c.is_ascii_digit()) {
+                continue;
+            }
+
+            if labels.insert(label, code.len() as u64).is_some() {
+                let message = format!("label `{}` is defined twice", label);
+                return Err(ParseError::at(source, offset, message).into());
+            }
+        }
+
+        let Some((offset, mnemonic)) = words.next() else {
+            continue;
+        };
+
+        let operand = words.next();
+        if let Some((offset, word)) = words.next() {
+            let message = format!("unexpected `{}` after the operand", word);
+            return Err(ParseError::at(source, offset, message).into());
+        }
+
+        if let Some(register) = mnemonic.strip_prefix('.') {
+            let Some(idx) = ["a", "b", "c"].iter().position(|name| *name == register) else {
+                let message = format!("unknown directive `{}`", mnemonic);
+                return Err(ParseError::at(source, offset, message).into());
+            };
+
+            let Some(value) = operand else {
+                let message = format!("`{}` expects the value of the register", mnemonic);
+                return Err(ParseError::at(source, offset, message).into());
+            };
+
+            registers[idx] = number(source, value, u64::MAX)?;
+            continue;
+        }
+
+        let Some(opcode) =
+            (0..8).find(|opcode| Instruction::decode(*opcode, 0).mnemonic() == mnemonic)
+        else {
+            let message = format!("unknown instruction `{}`", mnemonic);
+            return Err(ParseError::at(source, offset, message).into());
+        };
+
+        let value = match (Instruction::decode(opcode, 0), operand) {
+            (Instruction::BXC(_), None) => 0,
+            (Instruction::BXC(_), Some((offset, _))) => {
+                let message = "`bxc` does not take an operand";
+                return Err(ParseError::at(source, offset, message).into());
+            }
+            (_, None) => {
+                let message = format!("`{}` expects an operand", mnemonic);
+                return Err(ParseError::at(source, offset, message).into());
+            }
+            (Instruction::JNZ(_), Some((offset, word)))
+                if !word.chars().all(|c| c.is_ascii_digit()) =>
+            {
+                jumps.push((code.len() + 1, offset, word));
+                0
+            }
+            (instruction, Some(operand)) => match instruction.operand() {
+                Operand::Combo(_) => {
+                    match ["a", "b", "c"].iter().position(|name| *name == operand.1) {
+                        Some(idx) => idx as u64 + 4,
+                        None => number(source, operand, 3)?,
+                    }
+                }
+                _ => number(source, operand, 7)?,
+            },
+        };
+
+        code.extend([opcode, value]);
+    }
+
+    for (idx, offset, label) in jumps {
+        match labels.get(label) {
+            Some(address) if *address <= 7 => code[idx] = *address,
+            Some(address) => {
+                let message = format!("label `{}` at address {} is out of reach", label, address);
+                return Err(ParseError::at(source, offset, message).into());
+            }
+            None => {
+                let message = format!("unknown label `{}`", label);
+                return Err(ParseError::at(source, offset, message).into());
+            }
+        }
+    }
+
+    Ok(Assembly { registers, code })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        puzzles::{
+            day17::{disasm, Day17},
+            Puzzle, Solution,
+        },
+        Error,
+    };
+
+    #[test]
+    fn test_puzzle_day17_asm_assemble() {
+        let assembly = assemble(
+            "
+.a 729          ; the example of the puzzle
+loop:
+    adv 1
+    out a
+    jnz loop
+",
+        )
+        .unwrap();
+
+        assert_eq!(assembly.code, vec![0, 1, 5, 4, 3, 0]);
+        assert_eq!(
+            assembly.to_string(),
+            "Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,4,3,0"
+        );
+        assert_eq!(
+            Day17.part1(&assembly.to_string()).unwrap(),
+            String::from("4,6,3,5,6,3,5,2,1,0").into()
+        );
+    }
+
+    #[test]
+    fn test_puzzle_day17_asm_round_trip() {
+        let input = "Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: 2,4,1,1,7,5,4,4,1,4,0,3,5,5,3,0";
+        let listing = disasm::listing(&Day17.parse(input).unwrap().program);
+
+        // The operand of `bxc` is ignored, so it comes back as 0.
+        let assembly = assemble(&listing).unwrap();
+        assert_eq!(
+            assembly.code,
+            vec![2, 4, 1, 1, 7, 5, 4, 0, 1, 4, 0, 3, 5, 5, 3, 0]
+        );
+
+        let reassembled = Day17.parse(&assembly.to_string()).unwrap().program;
+        assert_eq!(disasm::listing(&reassembled), listing);
+    }
+
+    #[test]
+    fn test_puzzle_day17_asm_errors() {
+        let error = |source| match assemble(source) {
+            Err(Error::Parse(err)) => (err.line, err.column, err.message),
+            result => panic!("unexpected result {:?}", result),
+        };
+
+        assert_eq!(
+            error("adv 1\nout 4"),
+            (2, 5, String::from("expected a number up to 3, found `4`"))
+        );
+        assert_eq!(
+            error("bxl a"),
+            (1, 5, String::from("expected a number up to 7, found `a`"))
+        );
+        assert_eq!(
+            error("bxc 1"),
+            (1, 5, String::from("`bxc` does not take an operand"))
+        );
+        assert_eq!(
+            error("jmp 0"),
+            (1, 1, String::from("unknown instruction `jmp`"))
+        );
+        assert_eq!(
+            error("jnz end"),
+            (1, 5, String::from("unknown label `end`"))
+        );
+        assert_eq!(
+            error("x: adv 1\nx: out a"),
+            (2, 1, String::from("label `x` is defined twice"))
+        );
+        assert_eq!(
+            error("out a\nout a\nout a\nout a\nfar: out a\njnz far"),
+            (
+                6,
+                5,
+                String::from("label `far` at address 8 is out of reach")
+            )
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use std::{collections::BTreeSet, fmt};
+
+use super::{
+    symbolic::{Expr, Iteration},
+    Instruction, Operand, Program,
+};
+
+impl Instruction {
+    pub(super) fn mnemonic(self) -> &'static str {
+        match self {
+            Instruction::ADV(_) => "adv",
+            Instruction::BXL(_) => "bxl",
+            Instruction::BST(_) => "bst",
+            Instruction::JNZ(_) => "jnz",
+            Instruction::BXC(_) => "bxc",
+            Instruction::OUT(_) => "out",
+            Instruction::BDV(_) => "bdv",
+            Instruction::CDV(_) => "cdv",
+        }
+    }
+
+    pub(super) fn operand(self) -> Operand {
+        match self {
+            Instruction::ADV(operand)
+            | Instruction::BXL(operand)
+            | Instruction::BST(operand)
+            | Instruction::JNZ(operand)
+            | Instruction::BXC(operand)
+            | Instruction::OUT(operand)
+            | Instruction::BDV(operand)
+            | Instruction::CDV(operand) => operand,
+        }
+    }
+}
+
+/// Name of a combo operand: its literal value or the register it reads.
+fn combo_name(combo: u64) -> String {
+    match combo {
+        0..=3 => combo.to_string(),
+        4 => String::from("a"),
+        5 => String::from("b"),
+        6 => String::from("c"),
+        _ => format!("?{}", combo),
+    }
+}
+
+/// Prints the mnemonic with its decoded operand, e.g. `bst a`, `bxl 5` or `bxc`.
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.operand() {
+            Operand::Combo(combo) => write!(f, "{} {}", self.mnemonic(), combo_name(combo)),
+            Operand::Literal(literal) => write!(f, "{} {}", self.mnemonic(), literal),
+            Operand::Ignore(_) => write!(f, "{}", self.mnemonic()),
+        }
+    }
+}
+
+/// What an instruction does, written with the registers it reads and writes.
+fn effect(instruction: Instruction) -> String {
+    match instruction {
+        Instruction::ADV(Operand::Combo(combo)) => format!("a = a >> {}", combo_name(combo)),
+        Instruction::BXL(Operand::Literal(literal)) => format!("b = b ^ {}", literal),
+        Instruction::BST(Operand::Combo(combo)) => format!("b = {} % 8", combo_name(combo)),
+        Instruction::JNZ(Operand::Literal(literal)) => {
+            format!("if a != 0 jump to L{}", literal)
+        }
+        Instruction::BXC(_) => String::from("b = b ^ c"),
+        Instruction::OUT(Operand::Combo(combo)) => format!("out {} % 8", combo_name(combo)),
+        Instruction::BDV(Operand::Combo(combo)) => format!("b = a >> {}", combo_name(combo)),
+        Instruction::CDV(Operand::Combo(combo)) => format!("c = a >> {}", combo_name(combo)),
+        _ => String::from("invalid operand"),
+    }
+}
+
+/// One line per instruction with its address and effect, jump targets get a label.
+pub fn listing(program: &Program) -> String {
+    let targets: BTreeSet<u64> = program
+        .iter()
+        .filter_map(|instruction| match instruction {
+            Instruction::JNZ(Operand::Literal(target)) => Some(*target),
+            _ => None,
+        })
+        .collect();
+
+    let mut lines = vec![];
+    for (idx, instruction) in program.iter().enumerate() {
+        let address = idx as u64 * 2;
+        if targets.contains(&address) {
+            lines.push(format!("L{}:", address));
+        }
+
+        lines.push(format!(
+            "{:>4}: {:<8} ; {}",
+            address,
+            instruction.to_string(),
+            effect(*instruction)
+        ));
+    }
+
+    lines.join("\n")
+}
+
+/// The body of the program written as one expression per output and register, if it is a
+/// single loop jumping back to the start from its last instruction.
+///
+/// Every expression reads the registers as they were at the start of the iteration, and A is
+/// assigned last so the usual programs read top to bottom.
+pub fn pseudo_code(program: &Program) -> Option<String> {
+    let iteration = Iteration::of(program)?;
+
+    let mut lines: Vec<String> = iteration
+        .outputs
+        .iter()
+        .map(|output| format!("out {}", output))
+        .collect();
+
+    for (idx, register) in iteration.registers.iter().enumerate().rev() {
+        if *register != Expr::Register(idx) {
+            lines.push(format!("{} = {}", ["a", "b", "c"][idx], register));
+        }
+    }
+
+    if !iteration.looping {
+        return Some(lines.join("\n"));
+    }
+
+    Some(format!(
+        "do {{\n{}\n}} while a != 0",
+        lines
+            .iter()
+            .map(|line| format!("    {}", line))
+            .collect::<Vec<_>>()
+            .join("\n")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzles::{day17::Day17, Solution};
+
+    const PROGRAM: &str = "Register A: 0
+Register B: 0
+Register C: 0
+
+Program: 2,4,1,1,7,5,4,4,1,4,0,3,5,5,3,0";
+
+    #[test]
+    fn test_puzzle_day17_disasm_instruction() {
+        assert_eq!(Instruction::BST(Operand::Combo(4)).to_string(), "bst a");
+        assert_eq!(Instruction::BXL(Operand::Literal(5)).to_string(), "bxl 5");
+        assert_eq!(Instruction::CDV(Operand::Combo(5)).to_string(), "cdv b");
+        assert_eq!(Instruction::JNZ(Operand::Literal(0)).to_string(), "jnz 0");
+        assert_eq!(Instruction::BXC(Operand::Ignore(3)).to_string(), "bxc");
+        assert_eq!(Instruction::OUT(Operand::Combo(7)).to_string(), "out ?7");
+    }
+
+    #[test]
+    fn test_puzzle_day17_disasm_listing() {
+        let program = Day17.parse(PROGRAM).unwrap().program;
+        let listing = listing(&program);
+        let lines: Vec<&str> = listing.lines().collect();
+
+        assert_eq!(lines.len(), 9);
+        assert_eq!(lines[0], "L0:");
+        assert_eq!(lines[1], "   0: bst a    ; b = a % 8");
+        assert_eq!(lines[8], "  14: jnz 0    ; if a != 0 jump to L0");
+    }
+
+    #[test]
+    fn test_puzzle_day17_disasm_pseudo_code() {
+        let program = Day17.parse(PROGRAM).unwrap().program;
+
+        assert_eq!(
+            pseudo_code(&program).unwrap(),
+            "do {
+    out ((((a % 8) ^ 1) ^ (a >> ((a % 8) ^ 1))) ^ 4) % 8
+    c = a >> ((a % 8) ^ 1)
+    b = (((a % 8) ^ 1) ^ (a >> ((a % 8) ^ 1))) ^ 4
+    a = a >> 3
+} while a != 0"
+        );
+
+        let straight = vec![
+            Instruction::BXL(Operand::Literal(3)),
+            Instruction::OUT(Operand::Combo(5)),
+        ];
+        assert_eq!(
+            pseudo_code(&straight).unwrap(),
+            "out (b ^ 3) % 8\nb = b ^ 3"
+        );
+
+        let inner_loop = vec![
+            Instruction::OUT(Operand::Combo(4)),
+            Instruction::JNZ(Operand::Literal(2)),
+        ];
+        assert_eq!(pseudo_code(&inner_loop), None);
+    }
+}
@@ -0,0 +1,678 @@
+use super::{parse_input, Answer, Solution};
+use crate::{Error, Result};
+
+pub mod asm;
+mod disasm;
+mod symbolic;
+pub mod trace;
+
+use symbolic::Iteration;
+use trace::{Debugger, Step, TraceOptions};
+
+pub struct Day17;
+
+pub(crate) struct Input {
+    register_a: u64,
+    register_b: u64,
+    register_c: u64,
+
+    program: Program,
+    code: Vec<u64>,
+}
+
+type Program = Vec<Instruction>;
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Instruction {
+    ADV(Operand),
+    BXL(Operand),
+    BST(Operand),
+    JNZ(Operand),
+    BXC(Operand),
+    OUT(Operand),
+    BDV(Operand),
+    CDV(Operand),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Operand {
+    Combo(u64),
+    Literal(u64),
+    Ignore(u64),
+}
+
+impl Instruction {
+    /// Instruction of a 3-bit `opcode`, with its `operand` read the way the opcode expects.
+    fn decode(opcode: u64, operand: u64) -> Instruction {
+        match opcode {
+            0 => Instruction::ADV(Operand::Combo(operand)),
+            1 => Instruction::BXL(Operand::Literal(operand)),
+            2 => Instruction::BST(Operand::Combo(operand)),
+            3 => Instruction::JNZ(Operand::Literal(operand)),
+            4 => Instruction::BXC(Operand::Ignore(operand)),
+            5 => Instruction::OUT(Operand::Combo(operand)),
+            6 => Instruction::BDV(Operand::Combo(operand)),
+            _ => Instruction::CDV(Operand::Combo(operand)),
+        }
+    }
+}
+
+mod parser {
+    use nom::IResult;
+
+    use super::{Input, Instruction, Program};
+
+    fn parse_program(input: &str) -> IResult<&str, (Vec<u64>, Program)> {
+        nom::combinator::map_res(
+            nom::sequence::preceded(
+                nom::bytes::complete::tag("Program: "),
+                nom::multi::separated_list1(
+                    nom::bytes::complete::tag(","),
+                    nom::combinator::cut(nom::combinator::map(
+                        nom::character::complete::one_of("01234567"),
+                        |ch| ch as u64 - '0' as u64,
+                    )),
+                ),
+            ),
+            |code: Vec<u64>| {
+                if !code.len().is_multiple_of(2) {
+                    return Err("program ends with an opcode without operand");
+                }
+
+                let program = code
+                    .chunks(2)
+                    .map(|chunk| Instruction::decode(chunk[0], chunk[1]))
+                    .collect();
+
+                Ok((code, program))
+            },
+        )(input)
+    }
+
+    fn parse_register<'a>(input: &'a str, name: &'a str) -> IResult<&'a str, u64> {
+        nom::sequence::preceded(
+            nom::bytes::complete::tag(format!("Register {}: ", name).as_str()),
+            nom::character::complete::u64,
+        )(input)
+    }
+
+    fn parse_registers(input: &str) -> IResult<&str, (u64, u64, u64)> {
+        let (input, register_a) = parse_register(input, "A")?;
+        let (input, _) = nom::character::complete::newline(input)?;
+        let (input, register_b) = parse_register(input, "B")?;
+        let (input, _) = nom::character::complete::newline(input)?;
+        let (input, register_c) = parse_register(input, "C")?;
+
+        Ok((input, (register_a, register_b, register_c)))
+    }
+
+    pub fn parse(input: &str) -> IResult<&str, Input> {
+        nom::combinator::map_res(
+            nom::sequence::preceded(
+                nom::multi::many0(nom::character::complete::newline),
+                nom::sequence::separated_pair(
+                    parse_registers,
+                    nom::bytes::complete::tag("\n\n"),
+                    parse_program,
+                ),
+            ),
+            |((register_a, register_b, register_c), (code, program))| {
+                Ok::<Input, &str>(Input {
+                    register_a,
+                    register_b,
+                    register_c,
+                    program,
+                    code,
+                })
+            },
+        )(input)
+    }
+}
+
+#[derive(Debug)]
+struct Computer {
+    register_a: u64,
+    register_b: u64,
+    register_c: u64,
+
+    code: Vec<u64>,
+    /// Address in the code of the next opcode, which may be odd after a jump.
+    instruction_pointer: usize,
+    outputs: Vec<u64>,
+}
+
+impl Computer {
+    fn new(input: &Input) -> Self {
+        Computer {
+            register_a: input.register_a,
+            register_b: input.register_b,
+            register_c: input.register_c,
+            code: input.code.clone(),
+            instruction_pointer: 0,
+            outputs: Vec::new(),
+        }
+    }
+
+    /// Runs the program until it halts and returns its outputs.
+    fn run(mut self) -> Result<Vec<u64>> {
+        while self.step()?.is_some() {}
+
+        Ok(self.outputs)
+    }
+
+    /// Runs the program like [`Computer::run`], failing if it has not halted after `max_steps`
+    /// instructions.
+    fn run_for(mut self, max_steps: usize) -> Result<Vec<u64>> {
+        for _ in 0..max_steps {
+            if self.step()?.is_none() {
+                return Ok(self.outputs);
+            }
+        }
+
+        Err(Error::Solve(format!(
+            "the program did not halt after {} steps",
+            max_steps
+        )))
+    }
+
+    /// Executes the next instruction and records it, `None` once the program has halted.
+    fn step(&mut self) -> Result<Option<Step>> {
+        let address = self.address();
+        let before = self.registers();
+        let Some(instruction) = self.next_instruction() else {
+            return Ok(None);
+        };
+
+        let outputs = self.outputs.len();
+        self.execute_instruction(instruction)?;
+
+        Ok(Some(Step {
+            address,
+            instruction,
+            before,
+            after: self.registers(),
+            output: self.outputs.get(outputs).copied(),
+        }))
+    }
+
+    /// Address in the code of the next instruction.
+    fn address(&self) -> u64 {
+        self.instruction_pointer as u64
+    }
+
+    /// Whether no opcode and operand pair is left to read at the instruction pointer.
+    fn halted(&self) -> bool {
+        self.instruction_pointer + 1 >= self.code.len()
+    }
+
+    fn registers(&self) -> [u64; 3] {
+        [self.register_a, self.register_b, self.register_c]
+    }
+
+    fn next_instruction(&mut self) -> Option<Instruction> {
+        if self.halted() {
+            None
+        } else {
+            let address = self.instruction_pointer;
+            self.instruction_pointer += 2;
+            Some(Instruction::decode(
+                self.code[address],
+                self.code[address + 1],
+            ))
+        }
+    }
+
+    fn execute_instruction(&mut self, instruction: Instruction) -> Result<()> {
+        match instruction {
+            Instruction::ADV(Operand::Combo(combo)) => {
+                self.register_a = self.divide(combo)?;
+            }
+            Instruction::BXL(Operand::Literal(literal)) => {
+                self.register_b ^= literal;
+            }
+            Instruction::BST(Operand::Combo(combo)) => {
+                self.register_b = self.calc_combo(combo)? & 0b111;
+            }
+            Instruction::JNZ(Operand::Literal(literal)) => {
+                if self.register_a != 0 {
+                    self.jump(literal);
+                }
+            }
+            Instruction::BXC(Operand::Ignore(_)) => {
+                self.register_b ^= self.register_c;
+            }
+            Instruction::OUT(Operand::Combo(combo)) => {
+                let output = self.calc_combo(combo)? & 0b111;
+                self.outputs.push(output);
+            }
+            Instruction::BDV(Operand::Combo(combo)) => {
+                self.register_b = self.divide(combo)?;
+            }
+            Instruction::CDV(Operand::Combo(combo)) => {
+                self.register_c = self.divide(combo)?;
+            }
+            instruction => {
+                return Err(Error::Solve(format!(
+                    "invalid operand in {:?} at address {}",
+                    instruction,
+                    self.instruction_pointer - 2
+                )))
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Moves to the opcode at `address`, halting when no instruction is left there.
+    fn jump(&mut self, address: u64) {
+        self.instruction_pointer = usize::try_from(address)
+            .unwrap_or(usize::MAX)
+            .min(self.code.len());
+    }
+
+    /// Register A divided by 2 to the power of the combo operand, as a right shift.
+    fn divide(&self, combo: u64) -> Result<u64> {
+        let shift = self.calc_combo(combo)?;
+
+        Ok(u32::try_from(shift)
+            .ok()
+            .and_then(|shift| self.register_a.checked_shr(shift))
+            .unwrap_or(0))
+    }
+
+    fn calc_combo(&self, combo: u64) -> Result<u64> {
+        match combo {
+            0..=3 => Ok(combo),
+            4 => Ok(self.register_a),
+            5 => Ok(self.register_b),
+            6 => Ok(self.register_c),
+            _ => Err(Error::Solve(format!(
+                "invalid combo operand {} at address {}",
+                combo,
+                self.instruction_pointer - 2
+            ))),
+        }
+    }
+}
+
+impl Day17 {
+    /// Readable listing of the program, followed by its pseudo-code when it is a single loop.
+    pub fn disassemble(&self, input: &str) -> Result<String> {
+        let input = self.parse(input)?;
+        let mut text = format!(
+            "; a = {}, b = {}, c = {}\n{}",
+            input.register_a,
+            input.register_b,
+            input.register_c,
+            disasm::listing(&input.program)
+        );
+
+        if let Some(pseudo_code) = disasm::pseudo_code(&input.program) {
+            text.push_str("\n\n");
+            text.push_str(&pseudo_code);
+        }
+
+        Ok(text)
+    }
+
+    /// Table of every step of the program, stopping at the breakpoints and step limit of
+    /// `options`.
+    pub fn trace(&self, input: &str, options: &TraceOptions) -> Result<String> {
+        let input = self.parse(input)?;
+        let mut debugger = Debugger::new(Computer::new(&input), &options.breakpoints);
+        let (steps, stop) = debugger.run(options.max_steps)?;
+
+        Ok(trace::render(&steps, stop))
+    }
+
+    /// Steps after which a candidate of the part 2 search is considered to never halt.
+    const MAX_STEPS: usize = 1_000_000;
+
+    fn outputs_with_a(&self, input: &Input, register_a: u64) -> Result<Vec<u64>> {
+        Computer {
+            register_a,
+            ..Computer::new(input)
+        }
+        .run_for(Self::MAX_STEPS)
+    }
+
+    /// Values of register A whose last `levels` chunks of `bits` bits pass `matches`, built
+    /// from the highest chunk down.
+    ///
+    /// Each chunk is the value of A at the start of an iteration of a loop shifting A right by
+    /// `bits`, so the last outputs only depend on the highest bits of A.
+    fn search_register_a(
+        &self,
+        levels: usize,
+        bits: u64,
+        mut matches: impl FnMut(u64, usize) -> Result<bool>,
+    ) -> Result<Vec<u64>> {
+        let mut candidates = vec![0];
+
+        for level in (0..levels).rev() {
+            let mut next = vec![];
+            for a in candidates
+                .iter()
+                .flat_map(|a| (0..1 << bits).map(move |low| a << bits | low))
+            {
+                if matches(a, level)? {
+                    next.push(a);
+                }
+            }
+            candidates = next;
+        }
+
+        Ok(candidates)
+    }
+
+    /// Smallest value of register A making the program print itself.
+    ///
+    /// When the program loops shifting A right by a constant and prints values depending on A
+    /// only, the formulas of its outputs check each iteration without running it. Otherwise
+    /// A is assumed to lose 3 bits per output, and candidates are checked by running the
+    /// program on them.
+    fn find_quine(&self, input: &Input) -> Result<Option<u64>> {
+        let code = &input.code;
+        let iteration = Iteration::of(&input.program).filter(|iteration| {
+            iteration.looping
+                && !iteration.outputs.is_empty()
+                && code.len().is_multiple_of(iteration.outputs.len())
+                && iteration
+                    .shift()
+                    .is_some_and(|shift| (1..=16).contains(&shift))
+                && iteration
+                    .outputs
+                    .iter()
+                    .all(|output| !output.reads(1) && !output.reads(2))
+        });
+
+        let candidates = match iteration {
+            Some(iteration) => {
+                let outputs = iteration.outputs.len();
+                let bits = iteration.shift().unwrap_or(3);
+
+                self.search_register_a(code.len() / outputs, bits, |a, level| {
+                    let digits = &code[level * outputs..(level + 1) * outputs];
+                    Ok(a != 0
+                        && iteration
+                            .outputs
+                            .iter()
+                            .zip(digits)
+                            .all(|(output, digit)| output.eval([a, 0, 0]) == *digit))
+                })?
+            }
+            None => self.search_register_a(code.len(), 3, |a, level| {
+                Ok(self.outputs_with_a(input, a)? == code[level..])
+            })?,
+        };
+
+        let mut candidates: Vec<u64> = candidates.into_iter().filter(|a| *a > 0).collect();
+        candidates.sort_unstable();
+
+        for a in candidates {
+            if self.outputs_with_a(input, a)? == *code {
+                return Ok(Some(a));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+impl Solution for Day17 {
+    const DAY: i32 = 17;
+
+    type Input<'a> = Input;
+
+    fn parse(&self, input: &str) -> Result<Input> {
+        parse_input(input, parser::parse)
+    }
+
+    fn solve1(&self, input: &Self::Input<'_>) -> Result<Answer> {
+        Ok(Computer::new(input)
+            .run()?
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<String>>()
+            .join(",")
+            .into())
+    }
+
+    fn solve2(&self, input: &Self::Input<'_>) -> Result<Answer> {
+        self.find_quine(input)?
+            .map(Answer::from)
+            .ok_or_else(|| Error::Solve(String::from("no value of register A prints the program")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzles::Puzzle;
+
+    const TESTCASE: &str = r"
+Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,5,4,3,0";
+
+    const TESTCASE2: &str = r"
+Register A: 117440
+Register B: 0
+Register C: 0
+
+Program: 0,3,5,4,3,0";
+
+    fn program(register_a: u64, code: &str) -> String {
+        format!(
+            "Register A: {}\nRegister B: 0\nRegister C: 0\n\nProgram: {}",
+            register_a, code
+        )
+    }
+
+    #[test]
+    fn test_puzzle_day17_parse() {
+        let puzzle = Day17;
+        let input = puzzle.parse(TESTCASE).unwrap();
+        let program = vec![
+            Instruction::ADV(Operand::Combo(1)),
+            Instruction::OUT(Operand::Combo(4)),
+            Instruction::JNZ(Operand::Literal(0)),
+        ];
+
+        assert_eq!(input.register_a, 729);
+        assert_eq!(input.register_b, 0);
+        assert_eq!(input.register_c, 0);
+        assert_eq!(input.program, program);
+    }
+
+    #[test]
+    fn test_puzzle_day17_parse_error() {
+        let puzzle = Day17;
+
+        match puzzle.parse("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,9,4") {
+            Err(Error::Parse(err)) => {
+                assert_eq!((err.line, err.column), (5, 14));
+                assert_eq!(err.snippet, "Program: 0,1,9,4");
+            }
+            _ => panic!("expected a parse error"),
+        }
+
+        assert!(puzzle
+            .parse("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5")
+            .is_err());
+    }
+
+    #[test]
+    fn test_puzzle_day17_computer_execute() {
+        let mut computer = Computer {
+            register_a: 2024,
+            register_b: 0,
+            register_c: 0,
+            code: vec![0, 1, 5, 4, 3, 0],
+            instruction_pointer: 0,
+            outputs: Vec::new(),
+        };
+
+        while let Some(instruction) = computer.next_instruction() {
+            computer.execute_instruction(instruction).unwrap();
+        }
+
+        assert_eq!(
+            computer
+                .outputs
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+            String::from("4,2,5,6,7,7,7,7,3,1,0")
+        );
+    }
+
+    #[test]
+    fn test_puzzle_day17_computer_bxl() {
+        let mut computer = Computer {
+            register_a: 0,
+            register_b: 29,
+            register_c: 0,
+            code: vec![1, 7],
+            instruction_pointer: 0,
+            outputs: Vec::new(),
+        };
+
+        while let Some(instruction) = computer.next_instruction() {
+            computer.execute_instruction(instruction).unwrap();
+        }
+
+        assert_eq!(computer.register_b, 26);
+    }
+
+    #[test]
+    fn test_puzzle_day17_computer_bxc() {
+        let mut computer = Computer {
+            register_a: 0,
+            register_b: 2024,
+            register_c: 43690,
+            code: vec![4, 0],
+            instruction_pointer: 0,
+            outputs: Vec::new(),
+        };
+
+        while let Some(instruction) = computer.next_instruction() {
+            computer.execute_instruction(instruction).unwrap();
+        }
+
+        assert_eq!(computer.register_b, 44354);
+    }
+
+    #[test]
+    fn test_puzzle_day17_computer_wide_registers() {
+        let puzzle = Day17;
+
+        // 2^48 written in octal is 1 followed by 16 zeros.
+        assert_eq!(
+            puzzle.part1(&program(1 << 48, "5,4,0,3,3,0")).unwrap(),
+            String::from("0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,1").into()
+        );
+        assert_eq!(
+            puzzle.part1(&program(u64::MAX, "5,4")).unwrap(),
+            String::from("7").into()
+        );
+
+        // A shift by register A itself clears any register instead of overflowing.
+        assert_eq!(
+            puzzle.part1(&program(u64::MAX, "0,4,5,4")).unwrap(),
+            String::from("0").into()
+        );
+    }
+
+    #[test]
+    fn test_puzzle_day17_computer_invalid_combo() {
+        let puzzle = Day17;
+
+        match puzzle.part1("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 1,1,0,7") {
+            Err(Error::Solve(message)) => {
+                assert_eq!(message, "invalid combo operand 7 at address 2")
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_puzzle_day17_computer_jumps() {
+        let puzzle = Day17;
+
+        // With A at 0 the jump is skipped and the program carries on.
+        assert_eq!(
+            puzzle.part1(&program(0, "3,4,5,0")).unwrap(),
+            String::from("0").into()
+        );
+
+        // Jumping past the end of the program halts it.
+        assert_eq!(
+            puzzle.part1(&program(1, "3,4,5,0")).unwrap(),
+            String::new().into()
+        );
+        assert_eq!(
+            puzzle.part1(&program(1, "3,6")).unwrap(),
+            String::new().into()
+        );
+
+        // An odd target reads the opcode and operand from the middle of the instructions.
+        assert_eq!(
+            puzzle.part1(&program(1, "3,3,1,5,4,0")).unwrap(),
+            String::from("1").into()
+        );
+        assert_eq!(
+            puzzle.part1(&program(0, "3,3,1,5,4,0")).unwrap(),
+            String::new().into()
+        );
+        assert_eq!(
+            puzzle.part1(&program(1, "5,4,3,3")).unwrap(),
+            String::from("1").into()
+        );
+    }
+
+    #[test]
+    fn test_puzzle_day17_part1() {
+        let puzzle = Day17;
+
+        assert_eq!(
+            puzzle.part1(TESTCASE).unwrap(),
+            String::from("4,6,3,5,6,3,5,2,1,0").into()
+        );
+        assert_eq!(
+            puzzle.part1(TESTCASE2).unwrap(),
+            String::from("0,3,5,4,3,0").into()
+        );
+    }
+
+    #[test]
+    fn test_puzzle_day17_part2() {
+        let puzzle = Day17;
+
+        assert_eq!(puzzle.part2(TESTCASE2).unwrap(), 117440.into());
+
+        // Shape of the real inputs, where B and C are derived from the low bits of A.
+        let program = "2,4,1,1,7,5,4,4,1,4,0,3,5,5,3,0";
+        let input = format!(
+            "Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: {}",
+            program
+        );
+        let Answer::Integer(register_a) = puzzle.part2(&input).unwrap() else {
+            panic!("expected an integer answer");
+        };
+
+        let input = input.replacen("A: 0", &format!("A: {}", register_a), 1);
+        assert_eq!(puzzle.part1(&input).unwrap(), String::from(program).into());
+
+        let input = input.replacen(
+            &format!("A: {}", register_a),
+            &format!("A: {}", register_a - 1),
+            1,
+        );
+        assert_ne!(puzzle.part1(&input).unwrap(), String::from(program).into());
+    }
+}
@@ -0,0 +1,248 @@
+use std::fmt;
+
+use super::{Instruction, Operand, Program};
+
+/// Value computed by a program, in terms of the registers at the start of a loop iteration.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(super) enum Expr {
+    Const(u64),
+    /// Register A, B or C as `0`, `1` or `2`.
+    Register(usize),
+    Shr(Box<Expr>, Box<Expr>),
+    Xor(Box<Expr>, Box<Expr>),
+    /// Bitwise and with a constant mask.
+    Mask(Box<Expr>, u64),
+}
+
+/// Right shift where shifting every bit out gives 0, as the computer does.
+fn shift_right(value: u64, shift: u64) -> u64 {
+    u32::try_from(shift)
+        .ok()
+        .and_then(|shift| value.checked_shr(shift))
+        .unwrap_or(0)
+}
+
+impl Expr {
+    fn shr(value: Expr, shift: Expr) -> Expr {
+        match (value, shift) {
+            (Expr::Const(value), Expr::Const(shift)) => Expr::Const(shift_right(value, shift)),
+            (value, Expr::Const(0)) => value,
+            (Expr::Const(0), _) => Expr::Const(0),
+            (Expr::Shr(value, first), Expr::Const(second)) => match *first {
+                Expr::Const(first) => Expr::shr(*value, Expr::Const(first.saturating_add(second))),
+                first => Expr::Shr(
+                    Box::new(Expr::Shr(value, Box::new(first))),
+                    Box::new(Expr::Const(second)),
+                ),
+            },
+            (value, shift) => Expr::Shr(Box::new(value), Box::new(shift)),
+        }
+    }
+
+    fn xor(left: Expr, right: Expr) -> Expr {
+        match (left, right) {
+            (Expr::Const(left), Expr::Const(right)) => Expr::Const(left ^ right),
+            (value, Expr::Const(0)) | (Expr::Const(0), value) => value,
+            (Expr::Xor(value, first), Expr::Const(second)) => match *first {
+                Expr::Const(first) => Expr::xor(*value, Expr::Const(first ^ second)),
+                first => Expr::Xor(
+                    Box::new(Expr::Xor(value, Box::new(first))),
+                    Box::new(Expr::Const(second)),
+                ),
+            },
+            (left, right) => Expr::Xor(Box::new(left), Box::new(right)),
+        }
+    }
+
+    fn mask(value: Expr, mask: u64) -> Expr {
+        match value {
+            Expr::Const(value) => Expr::Const(value & mask),
+            Expr::Mask(value, inner) => Expr::mask(*value, inner & mask),
+            value => Expr::Mask(Box::new(value), mask),
+        }
+    }
+
+    /// Value of the expression for the given registers A to C.
+    pub fn eval(&self, registers: [u64; 3]) -> u64 {
+        match self {
+            Expr::Const(value) => *value,
+            Expr::Register(register) => registers[*register],
+            Expr::Shr(value, shift) => shift_right(value.eval(registers), shift.eval(registers)),
+            Expr::Xor(left, right) => left.eval(registers) ^ right.eval(registers),
+            Expr::Mask(value, mask) => value.eval(registers) & mask,
+        }
+    }
+
+    /// Whether the expression depends on `register`.
+    pub fn reads(&self, register: usize) -> bool {
+        match self {
+            Expr::Const(_) => false,
+            Expr::Register(other) => *other == register,
+            Expr::Shr(left, right) | Expr::Xor(left, right) => {
+                left.reads(register) || right.reads(register)
+            }
+            Expr::Mask(value, _) => value.reads(register),
+        }
+    }
+
+    /// Operand of an enclosing expression, in parentheses unless it is a leaf.
+    fn operand(&self) -> String {
+        match self {
+            Expr::Const(_) | Expr::Register(_) => self.to_string(),
+            _ => format!("({})", self),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Const(value) => write!(f, "{}", value),
+            Expr::Register(register) => write!(f, "{}", ["a", "b", "c"][*register]),
+            Expr::Shr(value, shift) => write!(f, "{} >> {}", value.operand(), shift.operand()),
+            Expr::Xor(left, right) => write!(f, "{} ^ {}", left.operand(), right.operand()),
+            Expr::Mask(value, mask) if mask.wrapping_add(1).is_power_of_two() => {
+                write!(f, "{} % {}", value.operand(), mask + 1)
+            }
+            Expr::Mask(value, mask) => write!(f, "{} & {}", value.operand(), mask),
+        }
+    }
+}
+
+/// Effect of one pass through the body of a program.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(super) struct Iteration {
+    /// Values emitted, in order.
+    pub outputs: Vec<Expr>,
+    /// Registers A to C at the end of the pass.
+    pub registers: [Expr; 3],
+    /// Whether the program jumps back to the start while A is not 0.
+    pub looping: bool,
+}
+
+impl Iteration {
+    /// Runs the body of `program` once on symbolic registers, if the program has no jump or
+    /// a single loop jumping back to the start from its last instruction.
+    pub fn of(program: &Program) -> Option<Iteration> {
+        let (last, body) = program.split_last()?;
+        let looping = match last {
+            Instruction::JNZ(Operand::Literal(0)) => true,
+            Instruction::JNZ(_) => return None,
+            _ => false,
+        };
+        let body = if looping { body } else { program };
+
+        let mut registers = [Expr::Register(0), Expr::Register(1), Expr::Register(2)];
+        let mut outputs = vec![];
+
+        for instruction in body {
+            let combo = |combo: u64| match combo {
+                0..=3 => Some(Expr::Const(combo)),
+                4..=6 => Some(registers[combo as usize - 4].clone()),
+                _ => None,
+            };
+
+            match *instruction {
+                Instruction::ADV(Operand::Combo(value)) => {
+                    registers[0] = Expr::shr(registers[0].clone(), combo(value)?);
+                }
+                Instruction::BXL(Operand::Literal(literal)) => {
+                    registers[1] = Expr::xor(registers[1].clone(), Expr::Const(literal));
+                }
+                Instruction::BST(Operand::Combo(value)) => {
+                    registers[1] = Expr::mask(combo(value)?, 0b111);
+                }
+                Instruction::BXC(_) => {
+                    registers[1] = Expr::xor(registers[1].clone(), registers[2].clone());
+                }
+                Instruction::OUT(Operand::Combo(value)) => {
+                    outputs.push(Expr::mask(combo(value)?, 0b111));
+                }
+                Instruction::BDV(Operand::Combo(value)) => {
+                    registers[1] = Expr::shr(registers[0].clone(), combo(value)?);
+                }
+                Instruction::CDV(Operand::Combo(value)) => {
+                    registers[2] = Expr::shr(registers[0].clone(), combo(value)?);
+                }
+                _ => return None,
+            }
+        }
+
+        Some(Iteration {
+            outputs,
+            registers,
+            looping,
+        })
+    }
+
+    /// Bits dropped from A by every pass, if A is only ever shifted right by a constant.
+    pub fn shift(&self) -> Option<u64> {
+        match &self.registers[0] {
+            Expr::Shr(value, shift) => match (value.as_ref(), shift.as_ref()) {
+                (Expr::Register(0), Expr::Const(shift)) => Some(*shift),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzles::{
+        day17::{asm::assemble, Computer, Day17},
+        Solution,
+    };
+
+    fn iteration(source: &str) -> Option<Iteration> {
+        let input = Day17.parse(&assemble(source).unwrap().to_string()).unwrap();
+        Iteration::of(&input.program)
+    }
+
+    #[test]
+    fn test_puzzle_day17_symbolic_iteration() {
+        let source = "bst a\nbxl 1\ncdv b\nbxc\nbxl 4\nadv 3\nout b\njnz 0";
+        let iteration = iteration(source).unwrap();
+
+        assert!(iteration.looping);
+        assert_eq!(iteration.shift(), Some(3));
+        assert_eq!(
+            iteration.outputs[0].to_string(),
+            "((((a % 8) ^ 1) ^ (a >> ((a % 8) ^ 1))) ^ 4) % 8"
+        );
+        assert!(!iteration.outputs[0].reads(1) && !iteration.outputs[0].reads(2));
+
+        // The formulas agree with the computer on the first pass through the loop.
+        let input = Day17.parse(&assemble(source).unwrap().to_string()).unwrap();
+        for a in [1, 7, 8, 2024, 117440, 1 << 40] {
+            let mut computer = Computer::new(&input);
+            computer.register_a = a;
+            while computer.address() != 14 {
+                computer.step().unwrap();
+            }
+
+            assert_eq!(iteration.outputs[0].eval([a, 0, 0]), computer.outputs[0]);
+            for (register, value) in iteration.registers.iter().zip(computer.registers()) {
+                assert_eq!(register.eval([a, 0, 0]), value);
+            }
+        }
+    }
+
+    #[test]
+    fn test_puzzle_day17_symbolic_simplify() {
+        let simplified = iteration("adv 3\nadv 3\nbxl 5\nbxl 5\nbst 3\nout 2").unwrap();
+
+        assert!(!simplified.looping);
+        assert_eq!(simplified.shift(), Some(6));
+        assert_eq!(simplified.registers[1], Expr::Const(3));
+        assert_eq!(simplified.outputs, vec![Expr::Const(2)]);
+
+        let dynamic = iteration("adv b\nout c").unwrap();
+        assert_eq!(dynamic.shift(), None);
+        assert!(dynamic.registers[0].reads(1));
+        assert!(dynamic.outputs[0].reads(2));
+
+        assert_eq!(iteration("out a\njnz 2"), None);
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::BTreeSet;
+
+use super::{Computer, Instruction};
+use crate::Result;
+
+/// Limits of a traced run.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TraceOptions {
+    /// Steps after which the run stops, so programs that never halt are caught.
+    pub max_steps: usize,
+    /// Addresses where the run stops before executing the instruction again.
+    pub breakpoints: Vec<u64>,
+}
+
+impl Default for TraceOptions {
+    fn default() -> Self {
+        Self {
+            max_steps: 10_000,
+            breakpoints: vec![],
+        }
+    }
+}
+
+/// One executed instruction with the state of the computer around it.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(super) struct Step {
+    pub address: u64,
+    pub instruction: Instruction,
+    pub before: [u64; 3],
+    pub after: [u64; 3],
+    pub output: Option<u64>,
+}
+
+/// Why a traced run stopped.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(super) enum Stop {
+    Halted,
+    Breakpoint(u64),
+    StepLimit(usize),
+}
+
+/// Runs a computer step by step, stopping at breakpoints.
+pub(super) struct Debugger {
+    computer: Computer,
+    breakpoints: BTreeSet<u64>,
+}
+
+impl Debugger {
+    pub fn new(computer: Computer, breakpoints: &[u64]) -> Self {
+        Self {
+            computer,
+            breakpoints: breakpoints.iter().copied().collect(),
+        }
+    }
+
+    /// Executes the next instruction, `None` once the program has halted.
+    pub fn step(&mut self) -> Result<Option<Step>> {
+        self.computer.step()
+    }
+
+    /// Steps until the program halts, reaches a breakpoint after the first step, or runs
+    /// `max_steps` instructions.
+    pub fn run(&mut self, max_steps: usize) -> Result<(Vec<Step>, Stop)> {
+        let mut steps = vec![];
+
+        while steps.len() < max_steps {
+            let address = self.computer.address();
+            if !steps.is_empty() && self.breakpoints.contains(&address) {
+                return Ok((steps, Stop::Breakpoint(address)));
+            }
+
+            match self.step()? {
+                Some(step) => steps.push(step),
+                None => return Ok((steps, Stop::Halted)),
+            }
+        }
+
+        if self.computer.halted() {
+            return Ok((steps, Stop::Halted));
+        }
+
+        Ok((steps, Stop::StepLimit(max_steps)))
+    }
+}
+
+/// Register cell of the table, showing the change made by the step.
+fn register(before: u64, after: u64) -> String {
+    if before == after {
+        before.to_string()
+    } else {
+        format!("{} -> {}", before, after)
+    }
+}
+
+/// Table of the steps, one row each, followed by the reason the run stopped.
+pub(super) fn render(steps: &[Step], stop: Stop) -> String {
+    let header = ["Step", "Address", "Instruction", "A", "B", "C", "Output"];
+    let rows: Vec<[String; 7]> = steps
+        .iter()
+        .enumerate()
+        .map(|(idx, step)| {
+            [
+                (idx + 1).to_string(),
+                step.address.to_string(),
+                step.instruction.to_string(),
+                register(step.before[0], step.after[0]),
+                register(step.before[1], step.after[1]),
+                register(step.before[2], step.after[2]),
+                step.output
+                    .map_or(String::new(), |output| output.to_string()),
+            ]
+        })
+        .collect();
+
+    let widths: Vec<usize> = (0..header.len())
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].len())
+                .chain([header[column].len()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let line = |cells: &[&str]| {
+        cells
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(column, (cell, width))| match column {
+                2 => format!("{:<width$}", cell, width = width),
+                _ => format!("{:>width$}", cell, width = width),
+            })
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![line(&header)];
+    for row in &rows {
+        lines.push(line(&row.each_ref().map(String::as_str)));
+    }
+
+    lines.push(match stop {
+        Stop::Halted => String::from("Halted"),
+        Stop::Breakpoint(address) => format!("Stopped at the breakpoint on address {}", address),
+        Stop::StepLimit(limit) => format!("Stopped after {} steps without halting", limit),
+    });
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzles::{
+        day17::{asm::assemble, Day17, Operand},
+        Solution,
+    };
+
+    fn debugger(source: &str, breakpoints: &[u64]) -> Debugger {
+        let input = Day17.parse(&assemble(source).unwrap().to_string()).unwrap();
+        Debugger::new(Computer::new(&input), breakpoints)
+    }
+
+    #[test]
+    fn test_puzzle_day17_trace_step() {
+        let mut debugger = debugger(".a 10\nbst a\nout b", &[]);
+
+        assert_eq!(
+            debugger.step().unwrap(),
+            Some(Step {
+                address: 0,
+                instruction: Instruction::BST(Operand::Combo(4)),
+                before: [10, 0, 0],
+                after: [10, 2, 0],
+                output: None,
+            })
+        );
+        assert_eq!(debugger.step().unwrap().unwrap().output, Some(2));
+        assert_eq!(debugger.step().unwrap(), None);
+    }
+
+    #[test]
+    fn test_puzzle_day17_trace_stops() {
+        let source = ".a 729\nloop: adv 1\nout a\njnz loop";
+
+        let (steps, stop) = debugger(source, &[]).run(1000).unwrap();
+        assert_eq!((steps.len(), stop), (30, Stop::Halted));
+
+        let mut stepper = debugger(source, &[0]);
+        let (steps, stop) = stepper.run(1000).unwrap();
+        assert_eq!((steps.len(), stop), (3, Stop::Breakpoint(0)));
+        let (steps, _) = stepper.run(1000).unwrap();
+        assert_eq!(steps[0].before[0], 364);
+
+        let (steps, stop) = debugger(".a 1\nloop: out a\njnz loop", &[]).run(5).unwrap();
+        assert_eq!((steps.len(), stop), (5, Stop::StepLimit(5)));
+
+        let (steps, stop) = debugger(".a 1\njnz 6", &[]).run(5).unwrap();
+        assert_eq!((steps.len(), stop), (1, Stop::Halted));
+    }
+
+    #[test]
+    fn test_puzzle_day17_trace_render() {
+        let (steps, stop) = debugger(".a 10\nbst a\nout b", &[]).run(10).unwrap();
+
+        assert_eq!(
+            render(&steps, stop),
+            "\
+Step  Address  Instruction   A       B  C  Output
+   1        0  bst a        10  0 -> 2  0
+   2        2  out b        10       2  0       2
+Halted"
+        );
+    }
+}
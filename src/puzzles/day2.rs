@@ -1,103 +1,238 @@
-use super::Puzzle;
+use std::fmt;
+
+use super::{parse_input, Answer, Solution};
+use crate::Result;
 
 pub struct Day2;
 
-impl Day2 {
-    fn parse(&self, input: &str) -> Vec<Vec<i32>> {
-        input
-            .trim()
-            .split("\n")
-            .map(|line| {
-                line.trim()
-                    .split(" ")
-                    .map(|level| level.parse().unwrap())
-                    .collect()
-            })
-            .collect()
+mod parser {
+    use nom::IResult;
+
+    pub fn parse(input: &str) -> IResult<&str, Vec<Vec<i32>>> {
+        nom::sequence::preceded(
+            nom::character::complete::multispace0,
+            nom::multi::separated_list1(
+                nom::character::complete::newline,
+                nom::multi::separated_list1(
+                    nom::character::complete::space1,
+                    nom::character::complete::i32,
+                ),
+            ),
+        )(input)
     }
+}
 
-    fn is_safe_report(&self, report: &Vec<i32>) -> bool {
-        let is_increase = report[0] < report[1];
+/// Why two adjacent levels make a report unsafe.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Reason {
+    Flat,
+    Jump,
+    DirectionChange,
+}
 
-        for idx in 1..report.len() {
-            if report[idx] == report[idx - 1] || (report[idx] - report[idx - 1]).abs() > 3 {
-                return false;
-            }
+/// First pair of adjacent levels breaking the rules, at `index` and `index + 1`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Violation {
+    pub index: usize,
+    pub levels: (i32, i32),
+    pub reason: Reason,
+}
 
-            if is_increase && report[idx] < report[idx - 1] {
-                return false;
-            }
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Verdict {
+    Safe,
+    /// Safe once the levels at these indices are removed.
+    SafeWithout(Vec<usize>),
+    Unsafe(Violation),
+}
 
-            if !is_increase && report[idx] > report[idx - 1] {
-                return false;
-            };
+impl Verdict {
+    pub fn is_safe(&self) -> bool {
+        !matches!(self, Verdict::Unsafe(_))
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Safe => write!(f, "safe"),
+            Verdict::SafeWithout(removed) => {
+                let removed: Vec<String> = removed.iter().map(|idx| idx.to_string()).collect();
+                let noun = if removed.len() == 1 {
+                    "index"
+                } else {
+                    "indices"
+                };
+                write!(f, "safe without {} {}", noun, removed.join(", "))
+            }
+            Verdict::Unsafe(Violation {
+                index,
+                levels: (from, to),
+                reason,
+            }) => {
+                let reason = match reason {
+                    Reason::Flat => String::from("stays flat"),
+                    Reason::Jump => format!("jumps by {}", (to - from).abs()),
+                    Reason::DirectionChange => String::from("changes direction"),
+                };
+                write!(
+                    f,
+                    "unsafe at index {}: {} -> {} {}",
+                    index, from, to, reason
+                )
+            }
         }
+    }
+}
+
+/// Report with the verdict on it.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Diagnosis {
+    pub levels: Vec<i32>,
+    pub verdict: Verdict,
+}
 
-        true
+/// Prints the levels followed by the verdict, e.g. `1 3 2 4 5: safe without index 1`.
+impl fmt::Display for Diagnosis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let levels: Vec<String> = self.levels.iter().map(|level| level.to_string()).collect();
+        write!(f, "{}: {}", levels.join(" "), self.verdict)
     }
+}
 
-    fn is_safe_tolerate(&self, report: &Vec<i32>) -> bool {
-        if self.is_safe_report(&report) {
-            return true;
-        }
+/// First pair of adjacent levels breaking the rules, the direction being set by the first
+/// pair.
+fn first_violation(report: &[i32]) -> Option<Violation> {
+    let direction = report
+        .windows(2)
+        .next()
+        .map(|pair| (pair[1] - pair[0]).signum())?;
 
-        let mut is_safe = false;
-        for idx in 0..report.len() {
-            let new_report: Vec<i32> = report
-                .iter()
-                .enumerate()
-                .filter_map(|(i, &x)| if i != idx { Some(x) } else { None })
-                .collect();
+    report.windows(2).enumerate().find_map(|(index, pair)| {
+        let step = pair[1] - pair[0];
+        let reason = if step == 0 {
+            Reason::Flat
+        } else if step.signum() != direction {
+            Reason::DirectionChange
+        } else if step.abs() > 3 {
+            Reason::Jump
+        } else {
+            return None;
+        };
+
+        Some(Violation {
+            index,
+            levels: (pair[0], pair[1]),
+            reason,
+        })
+    })
+}
+
+/// Fewest levels to remove so the report moves in `direction` by steps of 1 to 3, with the
+/// indices to remove, if there are at most `tolerance` of them.
+///
+/// Only the last `tolerance + 1` levels can precede a kept level, so this takes
+/// `O(len * tolerance)`.
+fn removals(report: &[i32], direction: i32, tolerance: usize) -> Option<Vec<usize>> {
+    let len = report.len();
+    // Removals needed with `idx` kept as the last level so far, and the level kept before it.
+    let mut removed = vec![usize::MAX; len];
+    let mut previous = vec![None; len];
+
+    for idx in 0..len {
+        removed[idx] = idx;
 
-            if self.is_safe_report(&new_report) {
-                is_safe = true;
-                break;
+        for prev in (idx.saturating_sub(tolerance + 1)..idx).rev() {
+            let step = (report[idx] - report[prev]) * direction;
+            let cost = removed[prev].saturating_add(idx - prev - 1);
+
+            if (1..=3).contains(&step) && cost < removed[idx] {
+                removed[idx] = cost;
+                previous[idx] = Some(prev);
             }
         }
+    }
 
-        is_safe
+    let (cost, last) = (0..len)
+        .map(|idx| (removed[idx].saturating_add(len - 1 - idx), idx))
+        .min()?;
+    if cost > tolerance {
+        return None;
     }
+
+    let mut kept = vec![false; len];
+    let mut current = Some(last);
+    while let Some(idx) = current {
+        kept[idx] = true;
+        current = previous[idx];
+    }
+
+    Some((0..len).filter(|idx| !kept[*idx]).collect())
 }
 
-impl Puzzle for Day2 {
-    type Output = usize;
+impl Day2 {
+    /// Verdict on `report` when up to `tolerance` of its levels may be removed.
+    pub fn analyse(report: &[i32], tolerance: usize) -> Verdict {
+        let Some(violation) = first_violation(report) else {
+            return Verdict::Safe;
+        };
 
-    fn part1(&self, input: &str) -> Self::Output {
-        let reports = self.parse(input);
+        let increasing = removals(report, 1, tolerance);
+        let decreasing = removals(report, -1, tolerance);
 
-        reports
-            .iter()
-            .map(|report| self.is_safe_report(&report))
-            .filter(|&b| b)
-            .count()
+        match (increasing, decreasing) {
+            (Some(increasing), Some(decreasing)) if decreasing.len() < increasing.len() => {
+                Verdict::SafeWithout(decreasing)
+            }
+            (Some(removed), _) | (None, Some(removed)) => Verdict::SafeWithout(removed),
+            (None, None) => Verdict::Unsafe(violation),
+        }
     }
 
-    fn part2(&self, input: &str) -> Self::Output {
-        let reports = self.parse(input);
+    /// Verdict on every report of `input`, with up to `tolerance` levels removed from each.
+    pub fn diagnose(&self, input: &str, tolerance: usize) -> Result<Vec<Diagnosis>> {
+        Ok(self
+            .parse(input)?
+            .into_iter()
+            .map(|levels| Diagnosis {
+                verdict: Self::analyse(&levels, tolerance),
+                levels,
+            })
+            .collect())
+    }
 
+    fn count_safe(&self, reports: &[Vec<i32>], tolerance: usize) -> usize {
         reports
             .iter()
-            .map(|report| self.is_safe_tolerate(&report))
-            .filter(|&b| b)
+            .filter(|report| Self::analyse(report, tolerance).is_safe())
             .count()
     }
+}
+
+impl Solution for Day2 {
+    const DAY: i32 = 2;
+
+    type Input<'a> = Vec<Vec<i32>>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Vec<i32>>> {
+        parse_input(input, parser::parse)
+    }
 
-    fn solve(&self, input: &str) {
-        let ans1 = self.part1(&input);
-        println!("Answer of Day 2 Part 1:  {:#?}", ans1);
+    fn solve1(&self, input: &Self::Input<'_>) -> Result<Answer> {
+        Ok(self.count_safe(input, 0).into())
+    }
 
-        let ans2 = self.part2(&input);
-        println!("Answer of Day 2 Part 2:  {:#?}", ans2);
+    fn solve2(&self, input: &Self::Input<'_>) -> Result<Answer> {
+        Ok(self.count_safe(input, 1).into())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::vec;
-
     use super::*;
+    use crate::puzzles::Puzzle;
 
-    const TESTCASE: &'static str = r"7 6 4 2 1
+    const TESTCASE: &str = r"7 6 4 2 1
 1 2 7 8 9
 9 7 6 2 1
 1 3 2 4 5
@@ -117,38 +252,152 @@ mod tests {
             vec![1, 3, 6, 7, 9],
         ];
 
-        assert_eq!(puzzle.parse(&TESTCASE), parse_result)
+        assert_eq!(puzzle.parse(TESTCASE).unwrap(), parse_result)
     }
 
     #[test]
     fn test_puzzle_day2_part1() {
         let puzzle = Day2;
 
-        let reports = puzzle.parse(&TESTCASE);
-        let mut result = vec![];
-
-        for report in reports {
-            let is_safe = puzzle.is_safe_report(&report);
-            result.push(is_safe);
-        }
-
-        assert_eq!(result, vec![true, false, false, false, false, true]);
-        assert_eq!(puzzle.part1(&TESTCASE), 2);
+        let diagnoses = puzzle.diagnose(TESTCASE, 0).unwrap();
+        assert_eq!(
+            diagnoses
+                .iter()
+                .map(|diagnosis| diagnosis.verdict.is_safe())
+                .collect::<Vec<_>>(),
+            vec![true, false, false, false, false, true]
+        );
+        assert_eq!(puzzle.part1(TESTCASE).unwrap(), 2.into());
     }
 
     #[test]
     fn test_puzzle_day2_part2() {
         let puzzle = Day2;
 
-        let reports = puzzle.parse(&TESTCASE);
-        let mut result = vec![];
+        let diagnoses = puzzle.diagnose(TESTCASE, 1).unwrap();
+        assert_eq!(
+            diagnoses
+                .iter()
+                .map(|diagnosis| diagnosis.verdict.is_safe())
+                .collect::<Vec<_>>(),
+            vec![true, false, false, true, true, true]
+        );
+        assert_eq!(puzzle.part2(TESTCASE).unwrap(), 4.into());
+    }
+
+    #[test]
+    fn test_puzzle_day2_analyse() {
+        let violation = |index, levels, reason| {
+            Verdict::Unsafe(Violation {
+                index,
+                levels,
+                reason,
+            })
+        };
+
+        assert_eq!(Day2::analyse(&[7, 6, 4, 2, 1], 1), Verdict::Safe);
+        assert_eq!(
+            Day2::analyse(&[1, 2, 7, 8, 9], 1),
+            violation(1, (2, 7), Reason::Jump)
+        );
+        assert_eq!(
+            Day2::analyse(&[8, 6, 4, 4, 1], 0),
+            violation(2, (4, 4), Reason::Flat)
+        );
+        assert_eq!(
+            Day2::analyse(&[1, 3, 2, 4, 5], 0),
+            violation(1, (3, 2), Reason::DirectionChange)
+        );
+        assert_eq!(
+            Day2::analyse(&[1, 3, 2, 4, 5], 1),
+            Verdict::SafeWithout(vec![1])
+        );
+        assert_eq!(
+            Day2::analyse(&[8, 6, 4, 4, 1], 1),
+            Verdict::SafeWithout(vec![2])
+        );
+
+        // The first level sets the wrong direction, removing it fixes the report.
+        assert_eq!(
+            Day2::analyse(&[5, 6, 4, 3, 1], 1),
+            Verdict::SafeWithout(vec![0])
+        );
+        assert_eq!(
+            Day2::analyse(&[1, 2, 3, 9], 1),
+            Verdict::SafeWithout(vec![3])
+        );
+
+        assert!(!Day2::analyse(&[1, 9, 9, 2, 3], 1).is_safe());
+        assert_eq!(
+            Day2::analyse(&[1, 9, 9, 2, 3], 2),
+            Verdict::SafeWithout(vec![1, 2])
+        );
+        assert_eq!(Day2::analyse(&[4], 0), Verdict::Safe);
+    }
+
+    #[test]
+    fn test_puzzle_day2_analyse_matches_brute_force() {
+        fn is_safe(report: &[i32]) -> bool {
+            first_violation(report).is_none()
+        }
 
-        for report in reports {
-            let is_safe = puzzle.is_safe_report(&report);
-            result.push(is_safe);
+        fn tolerates(report: &[i32], tolerance: usize) -> bool {
+            is_safe(report)
+                || (tolerance > 0
+                    && (0..report.len()).any(|idx| {
+                        let mut removed = report.to_vec();
+                        removed.remove(idx);
+                        tolerates(&removed, tolerance - 1)
+                    }))
         }
 
-        assert_eq!(result, vec![true, false, false, false, false, true]);
-        assert_eq!(puzzle.part2(&TESTCASE), 4);
+        let mut seed = 7_u64;
+        for _ in 0..2000 {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let len = (seed >> 60) as usize % 7 + 2;
+            let report: Vec<i32> = (0..len)
+                .map(|idx| ((seed >> (idx * 5)) % 9) as i32)
+                .collect();
+
+            for tolerance in 0..=2 {
+                let verdict = Day2::analyse(&report, tolerance);
+                assert_eq!(
+                    verdict.is_safe(),
+                    tolerates(&report, tolerance),
+                    "{:?}",
+                    report
+                );
+
+                if let Verdict::SafeWithout(removed) = verdict {
+                    assert!(removed.len() <= tolerance);
+                    let kept: Vec<i32> = (0..report.len())
+                        .filter(|idx| !removed.contains(idx))
+                        .map(|idx| report[idx])
+                        .collect();
+                    assert!(is_safe(&kept), "{:?} without {:?}", report, removed);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_puzzle_day2_explain() {
+        assert_eq!(
+            Day2.diagnose(TESTCASE, 1)
+                .unwrap()
+                .iter()
+                .map(Diagnosis::to_string)
+                .collect::<Vec<_>>()
+                .join("\n"),
+            "\
+7 6 4 2 1: safe
+1 2 7 8 9: unsafe at index 1: 2 -> 7 jumps by 5
+9 7 6 2 1: unsafe at index 2: 6 -> 2 jumps by 4
+1 3 2 4 5: safe without index 1
+8 6 4 4 1: safe without index 2
+1 3 6 7 9: safe"
+        );
     }
 }
@@ -1,242 +1,485 @@
-use super::Puzzle;
+use std::io::{self, Read};
+
+use super::{Answer, Solution};
+use crate::Result;
 
 pub struct Day3;
 
-struct Parser {
-    input: Vec<u8>,
-    pos: usize,
-    enable: bool,
+/// Most arguments an instruction can take.
+const MAX_ARGS: usize = 4;
+
+/// Instruction found in the corrupted memory.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Token {
+    Mul(u32, u32),
+    Do,
+    Dont,
 }
 
-impl Parser {
-    fn new(text: String) -> Self {
-        let input = text.as_bytes().to_owned();
+/// State the instructions act on while the memory is run.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct State {
+    pub enabled: bool,
+    pub total: i64,
+}
 
+impl Default for State {
+    fn default() -> Self {
         Self {
-            input,
-            pos: 0,
-            enable: true,
+            enabled: true,
+            total: 0,
         }
     }
+}
 
-    fn parse(&mut self, controlable: bool) -> Vec<Vec<i32>> {
-        let mut factors = vec![];
-        let length = self.input.len();
+/// Tokens of an [`InstructionSet`], each running its instruction on the state.
+pub trait Instruction {
+    fn execute(&self, state: &mut State);
+}
 
-        while self.pos < length {
-            let c = self.input[self.pos];
+impl Instruction for Token {
+    fn execute(&self, state: &mut State) {
+        match *self {
+            Token::Mul(left, right) if state.enabled => {
+                state.total += i64::from(left) * i64::from(right);
+            }
+            Token::Mul(..) => {}
+            Token::Do => state.enabled = true,
+            Token::Dont => state.enabled = false,
+        }
+    }
+}
 
-            match c {
-                b'd' => self.parse_enable(),
-                b'm' => self.parse_mul().map(|f| {
-                    if controlable && !self.enable {
-                        return;
-                    }
-                    factors.push(f)
-                }),
+/// Instruction written `name(a,b,...)` with `arity` arguments of 1 to 3 digits, building a
+/// token of type `T` from them.
+struct Pattern<T> {
+    name: &'static str,
+    arity: usize,
+    token: fn(&[u32]) -> T,
+}
 
-                _ => None,
-            };
+/// Reads an argument of 1 to 3 digits, longer numbers are not valid arguments.
+fn number(bytes: &[u8]) -> Option<(u32, &[u8])> {
+    let digits = bytes.iter().take_while(|b| b.is_ascii_digit()).count();
+    if !(1..=3).contains(&digits) {
+        return None;
+    }
 
-            self.pos += 1
-        }
+    let value = bytes[..digits]
+        .iter()
+        .fold(0, |value, digit| value * 10 + u32::from(digit - b'0'));
+
+    Some((value, &bytes[digits..]))
+}
 
-        factors
+impl<T> Pattern<T> {
+    /// Longest text of the instruction, which is also the lookahead needed to match it.
+    fn max_len(&self) -> usize {
+        self.name.len() + 2 + self.arity * 3 + self.arity.saturating_sub(1)
     }
 
-    fn next_char(&mut self) -> Option<u8> {
-        self.pos += 1;
+    /// Matches the instruction at the start of `bytes`, giving its token with its length.
+    fn match_at(&self, bytes: &[u8]) -> Option<(T, usize)> {
+        let mut rest = bytes
+            .strip_prefix(self.name.as_bytes())?
+            .strip_prefix(b"(")?;
+        let mut args = [0; MAX_ARGS];
 
-        if self.pos == self.input.len() {
-            None
-        } else {
-            Some(self.input[self.pos])
+        for (idx, arg) in args.iter_mut().take(self.arity).enumerate() {
+            if idx > 0 {
+                rest = rest.strip_prefix(b",")?;
+            }
+
+            let (value, next) = number(rest)?;
+            *arg = value;
+            rest = next;
         }
+
+        let rest = rest.strip_prefix(b")")?;
+        Some(((self.token)(&args[..self.arity]), bytes.len() - rest.len()))
     }
+}
+
+/// Instructions recognised in the memory, each building a token of type `T`.
+pub struct InstructionSet<T> {
+    patterns: Vec<Pattern<T>>,
+}
 
-    fn is_number(&self, char: char) -> bool {
-        return char >= '0' && char <= '9';
+impl<T> InstructionSet<T> {
+    pub fn new() -> Self {
+        Self { patterns: vec![] }
     }
 
-    fn fetch_number(&mut self) -> Option<i32> {
-        let mut number_string = String::from("");
+    /// Registers the instruction `name` taking `arity` arguments, turned into a token by
+    /// `token`.
+    ///
+    /// # Panics
+    ///
+    /// If `name` is empty or already registered, or `arity` is over 4.
+    pub fn with(mut self, name: &'static str, arity: usize, token: fn(&[u32]) -> T) -> Self {
+        assert!(!name.is_empty(), "instructions need a name");
+        assert!(
+            arity <= MAX_ARGS,
+            "instructions take at most {} arguments",
+            MAX_ARGS
+        );
+        assert!(
+            self.patterns.iter().all(|pattern| pattern.name != name),
+            "instruction {} is registered twice",
+            name
+        );
+
+        self.patterns.push(Pattern { name, arity, token });
+        self
+    }
 
-        while self.is_number(char::from(self.input[self.pos])) {
-            number_string.push(char::from(self.input[self.pos]));
+    /// Iterator over the tokens of the memory read from `reader`.
+    pub fn scan<R: Read>(&self, reader: R) -> Scanner<'_, T, R> {
+        Scanner::new(self, reader)
+    }
 
-            self.pos += 1;
-            if self.pos == self.input.len() {
-                return None;
-            }
+    /// Runs every instruction of the memory in order, starting from the default state.
+    pub fn run(&self, reader: impl Read) -> io::Result<State>
+    where
+        T: Instruction,
+    {
+        let mut state = State::default();
+
+        for token in self.scan(reader) {
+            let (_, token) = token?;
+            token.execute(&mut state);
         }
 
-        self.pos -= 1;
+        Ok(state)
+    }
 
-        if number_string.len() > 0 {
-            Some(number_string.parse().unwrap())
-        } else {
-            None
-        }
+    fn match_at(&self, bytes: &[u8]) -> Option<(T, usize)> {
+        self.patterns
+            .iter()
+            .find_map(|pattern| pattern.match_at(bytes))
     }
 
-    fn parse_enable(&mut self) -> Option<()> {
-        match self.next_char() {
-            Some(b'o') => (),
-            _ => return None,
-        };
+    fn starts_instruction(&self, byte: u8) -> bool {
+        self.patterns
+            .iter()
+            .any(|pattern| pattern.name.as_bytes()[0] == byte)
+    }
+}
 
-        match self.next_char() {
-            Some(b'(') => self.parse_do().map_or(Some(()), |_| None),
-            Some(b'n') => self.parse_do_not().map_or(Some(()), |_| None),
-            _ => return None,
-        }
+impl<T> Default for InstructionSet<T> {
+    fn default() -> Self {
+        Self::new()
     }
+}
 
-    fn parse_do(&mut self) -> Option<()> {
-        match self.next_char() {
-            Some(b')') => {
-                self.enable = true;
-                return Some(());
-            }
-            _ => (),
-        };
+/// Iterator over the tokens of corrupted memory read from a stream, with their byte offset.
+///
+/// The stream goes through a fixed-size buffer, so the memory is never held as a whole.
+pub struct Scanner<'a, T, R> {
+    instructions: &'a InstructionSet<T>,
+    lookahead: usize,
+    reader: R,
+    buffer: Box<[u8]>,
+    start: usize,
+    end: usize,
+    offset: usize,
+    eof: bool,
+}
 
-        None
-    }
+impl<'a, T, R: Read> Scanner<'a, T, R> {
+    const CAPACITY: usize = 8 * 1024;
 
-    fn parse_do_not(&mut self) -> Option<()> {
-        match self.next_char() {
-            Some(b'\'') => (),
-            _ => return None,
-        }
-        match self.next_char() {
-            Some(b't') => (),
-            _ => return None,
-        }
-        match self.next_char() {
-            Some(b'(') => (),
-            _ => return None,
+    fn new(instructions: &'a InstructionSet<T>, reader: R) -> Self {
+        let lookahead = instructions
+            .patterns
+            .iter()
+            .map(Pattern::max_len)
+            .max()
+            .unwrap_or(0);
+
+        Self {
+            instructions,
+            lookahead,
+            reader,
+            buffer: vec![0; Self::CAPACITY.max(lookahead)].into_boxed_slice(),
+            start: 0,
+            end: 0,
+            offset: 0,
+            eof: false,
         }
-        match self.next_char() {
-            Some(b')') => (),
-            _ => return None,
+    }
+
+    /// Reads more of the stream once fewer bytes than the longest instruction are buffered.
+    fn fill(&mut self) -> io::Result<()> {
+        if self.eof || self.end - self.start >= self.lookahead {
+            return Ok(());
         }
 
-        self.enable = false;
+        self.buffer.copy_within(self.start..self.end, 0);
+        self.end -= self.start;
+        self.start = 0;
+
+        while self.end < self.buffer.len() {
+            match self.reader.read(&mut self.buffer[self.end..]) {
+                Ok(0) => {
+                    self.eof = true;
+                    break;
+                }
+                Ok(read) => self.end += read,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+        }
 
-        Some(())
+        Ok(())
     }
 
-    fn parse_mul(&mut self) -> Option<Vec<i32>> {
-        match self.next_char() {
-            Some(b'u') => (),
-            _ => return None,
-        };
-        match self.next_char() {
-            Some(b'l') => (),
-            _ => return None,
-        };
-        match self.next_char() {
-            Some(b'(') => (),
-            _ => return None,
-        };
+    fn advance(&mut self, len: usize) {
+        self.start += len;
+        self.offset += len;
+    }
+}
 
-        let factor1;
-        let factor2;
+impl<T, R: Read> Iterator for Scanner<'_, T, R> {
+    type Item = io::Result<(usize, T)>;
 
-        self.pos += 1;
-        if self.pos == self.input.len() {
-            return None;
-        }
-        match self.fetch_number() {
-            Some(factor) => factor1 = factor,
-            None => return None,
-        }
+    fn next(&mut self) -> Option<Self::Item> {
+        let instructions = self.instructions;
 
-        match self.next_char() {
-            Some(b',') => (),
-            _ => return None,
-        };
+        loop {
+            if let Err(err) = self.fill() {
+                return Some(Err(err));
+            }
 
-        self.pos += 1;
-        if self.pos == self.input.len() {
-            return None;
-        }
-        match self.fetch_number() {
-            Some(factor) => factor2 = factor,
-            None => return None,
-        }
+            let bytes = &self.buffer[self.start..self.end];
+            if bytes.is_empty() {
+                return None;
+            }
 
-        match self.next_char() {
-            Some(b')') => (),
-            _ => return None,
-        };
+            if let Some((token, len)) = instructions.match_at(bytes) {
+                let offset = self.offset;
+                self.advance(len);
+                return Some(Ok((offset, token)));
+            }
 
-        Some(vec![factor1, factor2])
+            let skip = bytes[1..]
+                .iter()
+                .position(|byte| instructions.starts_instruction(*byte))
+                .map_or(bytes.len(), |idx| idx + 1);
+            self.advance(skip);
+        }
     }
 }
 
 impl Day3 {
-    fn parse(&self, input: &str, controlable: bool) -> Vec<Vec<i32>> {
-        let mut parser = Parser::new(String::from(input));
+    /// Instructions of part 1, where every `mul` counts.
+    pub fn part1_instructions() -> InstructionSet<Token> {
+        InstructionSet::new().with("mul", 2, |args| Token::Mul(args[0], args[1]))
+    }
 
-        parser.parse(controlable)
+    /// Instructions of part 2, where `do()` and `don't()` switch `mul` on and off.
+    pub fn part2_instructions() -> InstructionSet<Token> {
+        Self::part1_instructions()
+            .with("do", 0, |_| Token::Do)
+            .with("don't", 0, |_| Token::Dont)
     }
 }
 
-impl Puzzle for Day3 {
-    type Output = i32;
+impl Solution for Day3 {
+    const DAY: i32 = 3;
 
-    fn part1(&self, input: &str) -> Self::Output {
-        self.parse(input, false).iter().map(|v| v[0] * v[1]).sum()
-    }
+    /// The memory is scanned while solving, so parsing only checks it can be read.
+    type Input<'a> = &'a str;
 
-    fn part2(&self, input: &str) -> Self::Output {
-        self.parse(input, true).iter().map(|v| v[0] * v[1]).sum()
+    fn parse<'a>(&self, input: &'a str) -> Result<&'a str> {
+        for token in Self::part2_instructions().scan(input.as_bytes()) {
+            token?;
+        }
+
+        Ok(input)
     }
 
-    fn solve(&self, input: &str) {
-        let ans1 = self.part1(&input);
-        println!("Answer of Day 3 Part 1:  {:#?}", ans1);
+    fn solve1(&self, input: &Self::Input<'_>) -> Result<Answer> {
+        Ok(Self::part1_instructions()
+            .run(input.as_bytes())?
+            .total
+            .into())
+    }
 
-        let ans2 = self.part2(&input);
-        println!("Answer of Day 3 Part 2:  {:#?}", ans2);
+    fn solve2(&self, input: &Self::Input<'_>) -> Result<Answer> {
+        Ok(Self::part2_instructions()
+            .run(input.as_bytes())?
+            .total
+            .into())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::vec;
-
     use super::*;
+    use crate::puzzles::Puzzle;
 
-    const TESTCASE1: &'static str =
+    const TESTCASE1: &str =
         "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
 
-    const TESTCASE2: &'static str =
+    const TESTCASE2: &str =
         "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
 
+    /// Reader giving one byte at a time, so tokens are split across reads.
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match self.0.split_first() {
+                Some((byte, rest)) if !buf.is_empty() => {
+                    buf[0] = *byte;
+                    self.0 = rest;
+                    Ok(1)
+                }
+                _ => Ok(0),
+            }
+        }
+    }
+
+    fn tokens<T>(instructions: &InstructionSet<T>, input: &str) -> Vec<(usize, T)> {
+        instructions
+            .scan(input.as_bytes())
+            .collect::<io::Result<_>>()
+            .unwrap()
+    }
+
     #[test]
-    fn test_puzzle_day3_parse() {
-        let puzzle = Day3;
-        let parse_result1 = vec![vec![2, 4], vec![5, 5], vec![11, 8], vec![8, 5]];
-        let parse_result2 = vec![vec![2, 4], vec![8, 5]];
+    fn test_puzzle_day3_scanner() {
+        let instructions = Day3::part2_instructions();
+
+        assert_eq!(
+            tokens(&instructions, TESTCASE2),
+            vec![
+                (1, Token::Mul(2, 4)),
+                (20, Token::Dont),
+                (28, Token::Mul(5, 5)),
+                (48, Token::Mul(11, 8)),
+                (59, Token::Do),
+                (64, Token::Mul(8, 5)),
+            ]
+        );
+
+        let trickled: Vec<_> = instructions
+            .scan(Trickle(TESTCASE1.as_bytes()))
+            .collect::<io::Result<_>>()
+            .unwrap();
+        assert_eq!(trickled, tokens(&instructions, TESTCASE1));
+    }
+
+    #[test]
+    fn test_puzzle_day3_scanner_digits() {
+        let instructions = Day3::part2_instructions();
+        let tokens = |input| -> Vec<_> {
+            tokens(&instructions, input)
+                .into_iter()
+                .map(|(_, token)| token)
+                .collect()
+        };
+
+        assert_eq!(tokens("mul(123,4)"), vec![Token::Mul(123, 4)]);
+        assert_eq!(tokens("mul(1234,4)mul(,4)mul(4,)"), vec![]);
+        assert_eq!(
+            tokens("mumul(2,3)domul(1,1)"),
+            vec![Token::Mul(2, 3), Token::Mul(1, 1)]
+        );
+        assert_eq!(tokens("mul(2,3"), vec![]);
+        assert_eq!(tokens("do(1)mul(2,3,4)"), vec![]);
+    }
+
+    #[test]
+    fn test_puzzle_day3_scanner_large_input() {
+        let instructions = Day3::part2_instructions();
+        let input = TESTCASE1.repeat(500);
+
+        let tokens = tokens(&instructions, &input);
+        assert_eq!(tokens.len(), 2000);
+        assert_eq!(tokens[1999], (input.len() - 9, Token::Mul(8, 5)));
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    enum Arithmetic {
+        Add(u32, u32),
+        Sub(u32, u32),
+        Mad(u32, u32, u32),
+        On,
+        Off,
+    }
+
+    impl Instruction for Arithmetic {
+        fn execute(&self, state: &mut State) {
+            let value = match *self {
+                Arithmetic::On | Arithmetic::Off => {
+                    state.enabled = *self == Arithmetic::On;
+                    return;
+                }
+                Arithmetic::Add(left, right) => i64::from(left) + i64::from(right),
+                Arithmetic::Sub(left, right) => i64::from(left) - i64::from(right),
+                Arithmetic::Mad(left, right, add) => {
+                    i64::from(left) * i64::from(right) + i64::from(add)
+                }
+            };
+
+            if state.enabled {
+                state.total += value;
+            }
+        }
+    }
+
+    #[test]
+    fn test_puzzle_day3_instruction_set() {
+        let instructions = InstructionSet::new()
+            .with("add", 2, |args| Arithmetic::Add(args[0], args[1]))
+            .with("sub", 2, |args| Arithmetic::Sub(args[0], args[1]))
+            .with("mad", 3, |args| Arithmetic::Mad(args[0], args[1], args[2]))
+            .with("on", 0, |_| Arithmetic::On)
+            .with("off", 0, |_| Arithmetic::Off);
+
+        let input = "add(1,2)xsub(4,10)mul(2,2)off()add(5,5)on()mad(999,999,999)sub(1,2,3)";
+        assert_eq!(
+            tokens(&instructions, input)
+                .into_iter()
+                .map(|(_, token)| token)
+                .collect::<Vec<_>>(),
+            vec![
+                Arithmetic::Add(1, 2),
+                Arithmetic::Sub(4, 10),
+                Arithmetic::Off,
+                Arithmetic::Add(5, 5),
+                Arithmetic::On,
+                Arithmetic::Mad(999, 999, 999),
+            ]
+        );
+        assert_eq!(
+            instructions.run(Trickle(input.as_bytes())).unwrap(),
+            State {
+                enabled: true,
+                total: 3 - 6 + 999 * 999 + 999,
+            }
+        );
 
-        assert_eq!(puzzle.parse(&TESTCASE1, false), parse_result1);
-        assert_eq!(puzzle.parse(&TESTCASE2, true), parse_result2);
+        // Without `do()` and `don't()`, part 1 leaves every `mul` enabled.
+        let state = Day3::part1_instructions()
+            .run(TESTCASE2.as_bytes())
+            .unwrap();
+        assert_eq!(state.total, 161);
     }
 
     #[test]
     fn test_puzzle_day3_part1() {
         let puzzle = Day3;
 
-        assert_eq!(puzzle.part1(&TESTCASE1), 161);
+        assert_eq!(puzzle.part1(TESTCASE1).unwrap(), 161.into());
     }
 
     #[test]
     fn test_puzzle_day3_part2() {
         let puzzle = Day3;
 
-        assert_eq!(puzzle.part2(&TESTCASE2), 48);
+        assert_eq!(puzzle.part2(TESTCASE2).unwrap(), 48.into());
     }
 }
@@ -1,175 +1,63 @@
-use super::Puzzle;
+use super::{Answer, Solution};
+use crate::{
+    geometry::{Point, Vector},
+    grid::Grid,
+    Result,
+};
 
 pub struct Day4;
 
-impl<'puzzle> Day4 {
-    fn parse(&'puzzle self, input: &'puzzle str) -> Vec<Vec<&'puzzle str>> {
-        input
-            .trim()
-            .split("\n")
-            .map(|line| line.trim().split("").collect())
-            .map(|line: Vec<&str>| line[1..line.len() - 1].to_vec())
-            .collect()
-    }
-}
+impl Solution for Day4 {
+    const DAY: i32 = 4;
 
-impl Puzzle for Day4 {
-    type Output = i32;
-
-    fn part1(&self, input: &str) -> Self::Output {
-        let word_search = self.parse(&input);
-
-        let rows = word_search.len();
-        let cols = word_search[0].len();
-
-        let mut word_count = 0;
-
-        for row in 0..rows {
-            for col in 0..cols {
-                if word_search[row][col] != "X" {
-                    continue;
-                }
-
-                // Match horizontally and diagonally
-                if col >= 3 {
-                    if word_search[row][col - 1] == "M"
-                        && word_search[row][col - 2] == "A"
-                        && word_search[row][col - 3] == "S"
-                    {
-                        word_count += 1;
-                    }
-
-                    if row >= 3 {
-                        if word_search[row - 1][col - 1] == "M"
-                            && word_search[row - 2][col - 2] == "A"
-                            && word_search[row - 3][col - 3] == "S"
-                        {
-                            word_count += 1;
-                        }
-                    }
-
-                    if row + 3 < rows {
-                        if word_search[row + 1][col - 1] == "M"
-                            && word_search[row + 2][col - 2] == "A"
-                            && word_search[row + 3][col - 3] == "S"
-                        {
-                            word_count += 1;
-                        }
-                    }
-                }
-
-                if col + 3 < cols {
-                    if word_search[row][col + 1] == "M"
-                        && word_search[row][col + 2] == "A"
-                        && word_search[row][col + 3] == "S"
-                    {
-                        word_count += 1;
-                    }
-
-                    if row >= 3 {
-                        if word_search[row - 1][col + 1] == "M"
-                            && word_search[row - 2][col + 2] == "A"
-                            && word_search[row - 3][col + 3] == "S"
-                        {
-                            word_count += 1;
-                        }
-                    }
-
-                    if row + 3 < rows {
-                        if word_search[row + 1][col + 1] == "M"
-                            && word_search[row + 2][col + 2] == "A"
-                            && word_search[row + 3][col + 3] == "S"
-                        {
-                            word_count += 1;
-                        }
-                    }
-                }
-
-                // Match vertically
-                if row >= 3 {
-                    if word_search[row - 1][col] == "M"
-                        && word_search[row - 2][col] == "A"
-                        && word_search[row - 3][col] == "S"
-                    {
-                        word_count += 1;
-                    }
-                }
-
-                if row + 3 < rows {
-                    if word_search[row + 1][col] == "M"
-                        && word_search[row + 2][col] == "A"
-                        && word_search[row + 3][col] == "S"
-                    {
-                        word_count += 1;
-                    }
-                }
-            }
-        }
-
-        word_count
-    }
+    type Input<'a> = Grid<char>;
 
-    fn part2(&self, input: &str) -> Self::Output {
-        let word_search = self.parse(&input);
-
-        let rows = word_search.len();
-        let cols = word_search[0].len();
-
-        let mut word_count = 0;
-
-        for row in 1..rows - 1 {
-            for col in 1..cols - 1 {
-                if word_search[row][col] != "A" {
-                    continue;
-                }
-
-                if word_search[row - 1][col - 1] == "M"
-                    && word_search[row + 1][col - 1] == "M"
-                    && word_search[row - 1][col + 1] == "S"
-                    && word_search[row + 1][col + 1] == "S"
-                {
-                    word_count += 1;
-                } else if word_search[row - 1][col - 1] == "S"
-                    && word_search[row + 1][col - 1] == "S"
-                    && word_search[row - 1][col + 1] == "M"
-                    && word_search[row + 1][col + 1] == "M"
-                {
-                    word_count += 1;
-                } else if word_search[row - 1][col - 1] == "S"
-                    && word_search[row + 1][col - 1] == "M"
-                    && word_search[row - 1][col + 1] == "S"
-                    && word_search[row + 1][col + 1] == "M"
-                {
-                    word_count += 1;
-                } else if word_search[row - 1][col - 1] == "M"
-                    && word_search[row + 1][col - 1] == "S"
-                    && word_search[row - 1][col + 1] == "M"
-                    && word_search[row + 1][col + 1] == "S"
-                {
-                    word_count += 1;
-                }
-            }
-        }
-
-        word_count
+    fn parse(&self, input: &str) -> Result<Grid<char>> {
+        input.parse()
     }
 
-    fn solve(&self, input: &str) {
-        let ans1 = self.part1(&input);
-        println!("Answer of Day 4 Part 1:  {:#?}", ans1);
+    fn solve1(&self, word_search: &Self::Input<'_>) -> Result<Answer> {
+        let word_count = word_search
+            .find_all(&'X')
+            .flat_map(|start| Vector::ALL_DIRECTIONS.map(|step| (start, step)))
+            .filter(|&(start, step)| {
+                word_search
+                    .ray(start, step)
+                    .map(|(_, letter)| *letter)
+                    .take(4)
+                    .eq("XMAS".chars())
+            })
+            .count();
+
+        Ok(word_count.into())
+    }
 
-        let ans2 = self.part2(&input);
-        println!("Answer of Day 4 Part 2:  {:#?}", ans2);
+    fn solve2(&self, word_search: &Self::Input<'_>) -> Result<Answer> {
+        let is_mas = |first: Point, second: Point| {
+            matches!(
+                (word_search.get(first), word_search.get(second)),
+                (Some('M'), Some('S')) | (Some('S'), Some('M'))
+            )
+        };
+
+        let word_count = word_search
+            .find_all(&'A')
+            .filter(|&center| {
+                is_mas(center + Vector::new(-1, -1), center + Vector::new(1, 1))
+                    && is_mas(center + Vector::new(1, -1), center + Vector::new(-1, 1))
+            })
+            .count();
+
+        Ok(word_count.into())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::vec;
-
     use super::*;
+    use crate::puzzles::Puzzle;
 
-    const TESTCASE: &'static str = r"MMMSXXMASM
+    const TESTCASE: &str = r"MMMSXXMASM
 MSAMXMSMSA
 AMXSXMAAMM
 MSAMASMSMX
@@ -184,31 +72,34 @@ MXMXAXMASX
     #[test]
     fn test_puzzle_day4_parse() {
         let puzzle = Day4;
-        let parse_result = vec![
-            vec!["M", "M", "M", "S", "X", "X", "M", "A", "S", "M"],
-            vec!["M", "S", "A", "M", "X", "M", "S", "M", "S", "A"],
-            vec!["A", "M", "X", "S", "X", "M", "A", "A", "M", "M"],
-            vec!["M", "S", "A", "M", "A", "S", "M", "S", "M", "X"],
-            vec!["X", "M", "A", "S", "A", "M", "X", "A", "M", "M"],
-            vec!["X", "X", "A", "M", "M", "X", "X", "A", "M", "A"],
-            vec!["S", "M", "S", "M", "S", "A", "S", "X", "S", "S"],
-            vec!["S", "A", "X", "A", "M", "A", "S", "A", "A", "A"],
-            vec!["M", "A", "M", "M", "M", "X", "M", "M", "M", "M"],
-            vec!["M", "X", "M", "X", "A", "X", "M", "A", "S", "X"],
-        ];
-
-        assert_eq!(puzzle.parse(&TESTCASE), parse_result)
+        let word_search = puzzle.parse(TESTCASE).unwrap();
+
+        assert_eq!((word_search.width(), word_search.height()), (10, 10));
+        assert_eq!(
+            word_search.row(0).unwrap().collect::<String>(),
+            "MMMSXXMASM"
+        );
+        assert_eq!(
+            word_search.row(9).unwrap().collect::<String>(),
+            "MXMXAXMASX"
+        );
+        assert_eq!(
+            word_search.column(0).unwrap().collect::<String>(),
+            "MMAMXXSSMM"
+        );
+
+        assert!(puzzle.parse("XMAS\nXMA\n").is_err())
     }
 
     #[test]
     fn test_puzzle_day4_part1() {
         let puzzle = Day4;
-        assert_eq!(puzzle.part1(&TESTCASE), 18);
+        assert_eq!(puzzle.part1(TESTCASE).unwrap(), 18.into());
     }
 
     #[test]
     fn test_puzzle_day4_part2() {
         let puzzle = Day4;
-        assert_eq!(puzzle.part2(&TESTCASE), 9);
+        assert_eq!(puzzle.part2(TESTCASE).unwrap(), 9.into());
     }
 }
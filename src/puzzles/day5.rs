@@ -1,9 +1,12 @@
 use std::collections::HashMap;
 
-use super::Puzzle;
+use super::{parse_input, Answer, Solution};
+use crate::Result;
 
 pub struct Day5;
 
+type Input = (Vec<(i32, i32)>, Vec<Vec<i32>>);
+
 mod parser {
     use nom::{
         bytes::complete::tag,
@@ -13,6 +16,8 @@ mod parser {
         IResult,
     };
 
+    use super::Input;
+
     fn parse_page_ordering_rules(input: &str) -> IResult<&str, (i32, i32)> {
         separated_pair(
             nom::character::complete::i32,
@@ -28,7 +33,7 @@ mod parser {
         )(input)
     }
 
-    pub fn input_parser(input: &str) -> IResult<&str, (Vec<(i32, i32)>, Vec<Vec<i32>>)> {
+    pub fn input_parser(input: &str) -> IResult<&str, Input> {
         separated_pair(
             separated_list1(newline, parse_page_ordering_rules),
             many1(newline),
@@ -37,19 +42,17 @@ mod parser {
     }
 }
 
-impl Day5 {
-    fn parse(&self, input: &str) -> Result<(Vec<(i32, i32)>, Vec<Vec<i32>>), String> {
-        let (_, parse_result) = parser::input_parser(input).map_err(|err| format!("{:#?}", err))?;
+impl Solution for Day5 {
+    const DAY: i32 = 5;
 
-        Ok(parse_result)
-    }
-}
+    type Input<'a> = Input;
 
-impl Puzzle for Day5 {
-    type Output = Result<i32, String>;
+    fn parse(&self, input: &str) -> Result<Input> {
+        parse_input(input, parser::input_parser)
+    }
 
-    fn part1(&self, input: &str) -> Self::Output {
-        let (page_ordering_rules, page_updates) = self.parse(input)?;
+    fn solve1(&self, input: &Self::Input<'_>) -> Result<Answer> {
+        let (page_ordering_rules, page_updates) = input;
         let mut order_map = HashMap::<i32, Vec<i32>>::new();
 
         page_ordering_rules
@@ -70,11 +73,7 @@ impl Puzzle for Day5 {
                     }
                 };
 
-                if !update[idx + 1..]
-                    .iter()
-                    .map(|num| follows.contains(num))
-                    .all(|x| x)
-                {
+                if !update[idx + 1..].iter().all(|num| follows.contains(num)) {
                     is_right = false;
                     break;
                 }
@@ -85,11 +84,11 @@ impl Puzzle for Day5 {
             }
         }
 
-        Ok(middle_sum)
+        Ok(middle_sum.into())
     }
 
-    fn part2(&self, input: &str) -> Self::Output {
-        let (page_ordering_rules, page_updates) = self.parse(input)?;
+    fn solve2(&self, input: &Self::Input<'_>) -> Result<Answer> {
+        let (page_ordering_rules, page_updates) = input;
         let mut order_map = HashMap::<i32, Vec<i32>>::new();
 
         page_ordering_rules
@@ -109,9 +108,7 @@ impl Puzzle for Day5 {
                     {
                         is_incorrect = true;
 
-                        let temp = sorted_update[i];
-                        sorted_update[i] = sorted_update[j];
-                        sorted_update[j] = temp;
+                        sorted_update.swap(i, j);
                     }
                 }
             }
@@ -121,15 +118,7 @@ impl Puzzle for Day5 {
             }
         }
 
-        Ok(incorrect_middle_sum)
-    }
-
-    fn solve(&self, input: &str) {
-        let ans1 = self.part1(&input);
-        println!("Answer of Day 5 Part 1:  {:#?}", ans1.unwrap());
-
-        let ans2 = self.part2(&input);
-        println!("Answer of Day 5 Part 2:  {:#?}", ans2.unwrap());
+        Ok(incorrect_middle_sum.into())
     }
 }
 
@@ -138,8 +127,9 @@ mod tests {
     use std::vec;
 
     use super::*;
+    use crate::puzzles::Puzzle;
 
-    const TESTCASE: &'static str = r"47|53
+    const TESTCASE: &str = r"47|53
 97|13
 97|61
 97|47
@@ -204,9 +194,9 @@ mod tests {
             vec![97, 13, 75, 29, 47],
         ];
 
-        assert!(puzzle.parse(&TESTCASE).is_ok());
+        assert!(puzzle.parse(TESTCASE).is_ok());
 
-        let (parse_rules, parse_updates) = puzzle.parse(&TESTCASE).unwrap();
+        let (parse_rules, parse_updates) = puzzle.parse(TESTCASE).unwrap();
 
         assert_eq!(page_ordering_rules, parse_rules);
         assert_eq!(page_updates, parse_updates);
@@ -215,12 +205,12 @@ mod tests {
     #[test]
     fn test_puzzle_day5_part1() {
         let puzzle = Day5;
-        assert_eq!(puzzle.part1(&TESTCASE).unwrap(), 143);
+        assert_eq!(puzzle.part1(TESTCASE).unwrap(), 143.into());
     }
 
     #[test]
     fn test_puzzle_day5_part2() {
         let puzzle = Day5;
-        assert_eq!(puzzle.part2(&TESTCASE).unwrap(), 123);
+        assert_eq!(puzzle.part2(TESTCASE).unwrap(), 123.into());
     }
 }
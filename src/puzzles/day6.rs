@@ -1,249 +1,127 @@
 use std::collections::HashSet;
 
-use super::Puzzle;
+use super::{Answer, Solution};
+use crate::{
+    error::ParseError,
+    geometry::{Direction, Point},
+    grid::Grid,
+    Error, Result,
+};
 
 pub struct Day6;
 
-struct Input {
-    map_size: (usize, usize),
-    guard_position: (usize, usize),
-    obstacle_positions: Vec<(usize, usize)>,
+pub(crate) struct Input {
+    map: Grid<Ceil>,
+    guard_position: Point,
 }
 
-mod parser {
-    use nom::IResult;
-
-    pub enum Ceil {
-        Guard,
-        Obstacle,
-        Empty,
-    }
-
-    fn parse_ceil(input: &str) -> IResult<&str, Ceil> {
-        nom::combinator::map_res(nom::character::complete::anychar, |c| match c {
-            '#' => Ok(Ceil::Obstacle),
-            '^' => Ok(Ceil::Guard),
-            '.' => Ok(Ceil::Empty),
-            _ => Err("Invalid ceil"),
-        })(input)
-    }
-
-    pub fn parse_row(input: &str) -> IResult<&str, Vec<Ceil>> {
-        nom::multi::many1(parse_ceil)(input)
-    }
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Ceil {
+    Obstacle,
+    Empty,
 }
 
 impl Day6 {
-    fn parse(&self, input: &str) -> Result<Input, String> {
-        let (_, map) = nom::multi::separated_list1(
-            nom::character::complete::newline,
-            parser::parse_row,
-        )(input)
-        .map_err(|err| format!("{:#?}", err))?;
-
-        let mut guard_position = (0, 0);
-        let mut obstacle_positions = vec![];
-
-        map.iter().enumerate().for_each(|(row_idx, row)| {
-            row.iter()
-                .enumerate()
-                .for_each(|(col_idx, ceil)| match ceil {
-                    parser::Ceil::Guard => guard_position = (row_idx, col_idx),
-                    parser::Ceil::Obstacle => obstacle_positions.push((row_idx, col_idx)),
-                    _ => (),
-                })
-        });
-
-        Ok(Input {
-            map_size: (map.len(), map[0].len()),
-            guard_position,
-            obstacle_positions,
-        })
-    }
-
-    fn next_pos(
-        &self,
-        cur_pos: (usize, usize),
-        direction: &Direction,
-        size: (usize, usize),
-    ) -> Option<(usize, usize)> {
-        let (row, col) = cur_pos;
-        let (m, n) = size;
-
-        match direction {
-            Direction::Up => {
-                if row == 0 {
-                    None
-                } else {
-                    Some((row - 1, col))
-                }
-            }
-            Direction::Down => {
-                if row == m - 1 {
-                    None
-                } else {
-                    Some((row + 1, col))
-                }
-            }
-            Direction::Left => {
-                if col == 0 {
-                    None
-                } else {
-                    Some((row, col - 1))
-                }
-            }
-            Direction::Right => {
-                if col == n - 1 {
-                    None
-                } else {
-                    Some((row, col + 1))
-                }
-            }
-        }
-    }
-
-    fn next_direction(&self, direction: &Direction) -> Direction {
-        match *direction {
-            Direction::Up => Direction::Right,
-            Direction::Right => Direction::Down,
-            Direction::Down => Direction::Left,
-            Direction::Left => Direction::Up,
-        }
-    }
-
-    fn check_guard_loop(
-        &self,
-        cur_pos: (usize, usize),
-        direction: Direction,
-        size: (usize, usize),
-        obstacle_positions: Vec<(usize, usize)>,
-    ) -> bool {
-        let mut cur_pos = cur_pos.clone();
-        let mut direction = direction.clone();
+    /// Positions visited by the guard in order with its direction, `None` if it loops.
+    fn walk(&self, map: &Grid<Ceil>, start: Point) -> Option<Vec<(Point, Direction)>> {
+        let mut position = start;
+        let mut direction = Direction::Up;
         let mut visited = HashSet::new();
+        let mut path = vec![];
 
         loop {
-            match self.next_pos(cur_pos, &direction, size) {
-                Some(pos) => {
-                    if obstacle_positions.contains(&pos) {
-                        direction = self.next_direction(&direction);
-                    } else {
-                        if visited.contains(&(cur_pos, direction.clone())) {
-                            return true;
-                        }
-
-                        visited.insert((cur_pos, direction.clone()));
-                        cur_pos = pos;
-                    }
-                }
+            if !visited.insert((position, direction)) {
+                return None;
+            }
+            path.push((position, direction));
 
-                None => {
-                    return false;
-                }
+            match map.get(position + direction) {
+                Some(Ceil::Obstacle) => direction = direction.turn_right(),
+                Some(Ceil::Empty) => position += direction,
+                None => return Some(path),
             }
         }
     }
 }
 
-#[derive(Eq, PartialEq, Hash, Clone, Debug)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Puzzle for Day6 {
-    type Output = Result<usize, String>;
-
-    fn part1(&self, input: &str) -> Self::Output {
-        let Input {
-            map_size,
-            guard_position,
-            obstacle_positions,
-        } = self.parse(input)?;
-
-        let mut direction = Direction::Up;
-        let mut cur_pos = guard_position.clone();
-        let mut visited = HashSet::<(usize, usize)>::new();
+impl Solution for Day6 {
+    const DAY: i32 = 6;
 
-        visited.insert(cur_pos);
+    type Input<'a> = Input;
 
-        loop {
-            match self.next_pos(cur_pos, &direction, map_size) {
-                Some(pos) => {
-                    if obstacle_positions.contains(&pos) {
-                        direction = self.next_direction(&direction);
-                    } else {
-                        cur_pos = pos;
-                        visited.insert(pos);
-                    }
-                }
+    fn parse(&self, input: &str) -> Result<Input> {
+        let map = Grid::parse(input, |c| match c {
+            '#' | '.' | '^' => Some(c),
+            _ => None,
+        })?;
 
-                None => break,
-            }
-        }
+        let guard_position = map
+            .find(&'^')
+            .ok_or_else(|| ParseError::at(input, input.len(), "expected a guard `^` on the map"))?;
 
-        Ok(visited.len())
+        Ok(Input {
+            map: map.map(|&c| match c {
+                '#' => Ceil::Obstacle,
+                _ => Ceil::Empty,
+            }),
+            guard_position,
+        })
     }
 
-    fn part2(&self, input: &str) -> Self::Output {
+    fn solve1(&self, input: &Self::Input<'_>) -> Result<Answer> {
         let Input {
-            map_size,
+            map,
             guard_position,
-            obstacle_positions,
-        } = self.parse(input)?;
+        } = input;
 
-        let mut direction = Direction::Up;
-        let mut cur_pos = guard_position.clone();
-        let mut path = HashSet::new();
+        let path = self
+            .walk(map, *guard_position)
+            .ok_or_else(|| Error::Solve(String::from("the guard walks in a loop")))?;
 
-        path.insert(guard_position);
+        Ok(path
+            .iter()
+            .map(|(position, _)| *position)
+            .collect::<HashSet<Point>>()
+            .len()
+            .into())
+    }
 
-        loop {
-            match self.next_pos(cur_pos, &direction, map_size) {
-                Some(pos) => {
-                    if obstacle_positions.contains(&pos) {
-                        direction = self.next_direction(&direction);
-                    } else {
-                        cur_pos = pos;
-                        path.insert(pos);
-                    }
-                }
+    fn solve2(&self, input: &Self::Input<'_>) -> Result<Answer> {
+        let Input {
+            map,
+            guard_position,
+        } = input;
+        let (mut map, guard_position) = (map.clone(), *guard_position);
 
-                None => break,
-            }
-        }
+        let mut path: HashSet<Point> = self
+            .walk(&map, guard_position)
+            .ok_or_else(|| Error::Solve(String::from("the guard walks in a loop")))?
+            .into_iter()
+            .map(|(position, _)| position)
+            .collect();
 
         path.remove(&guard_position);
 
         Ok(path
             .iter()
-            .filter(|&pos| {
-                let mut obstacle_pos = obstacle_positions.clone();
-                obstacle_pos.push(*pos);
+            .filter(|&&position| {
+                map[position] = Ceil::Obstacle;
+                let is_loop = self.walk(&map, guard_position).is_none();
+                map[position] = Ceil::Empty;
 
-                self.check_guard_loop(guard_position, Direction::Up, map_size, obstacle_pos)
+                is_loop
             })
-            .count())
-    }
-
-    fn solve(&self, input: &str) {
-        let ans1 = self.part1(&input);
-        println!("Answer of Day 6 Part 1:  {:#?}", ans1.unwrap());
-
-        let ans2 = self.part2(&input);
-        println!("Answer of Day 6 Part 2:  {:#?}", ans2.unwrap());
+            .count()
+            .into())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::vec;
-
     use super::*;
+    use crate::puzzles::Puzzle;
 
-    const TESTCASE: &'static str = r"....#.....
+    const TESTCASE: &str = r"....#.....
 .........#
 ..........
 ..#.......
@@ -258,33 +136,35 @@ mod tests {
     #[test]
     fn test_puzzle_day6_parse() {
         let puzzle = Day6;
-        let guard_position = (6, 4);
         let obstacle_positions = vec![
-            (0, 4),
-            (1, 9),
-            (3, 2),
-            (4, 7),
-            (6, 1),
-            (7, 8),
-            (8, 0),
-            (9, 6),
+            Point::new(4, 0),
+            Point::new(9, 1),
+            Point::new(2, 3),
+            Point::new(7, 4),
+            Point::new(1, 6),
+            Point::new(8, 7),
+            Point::new(0, 8),
+            Point::new(6, 9),
         ];
 
-        let input = puzzle.parse(&TESTCASE).unwrap();
+        let input = puzzle.parse(TESTCASE).unwrap();
 
-        assert_eq!(input.guard_position, guard_position);
-        assert_eq!(input.obstacle_positions, obstacle_positions);
+        assert_eq!(input.guard_position, Point::new(4, 6));
+        assert_eq!(
+            input.map.find_all(&Ceil::Obstacle).collect::<Vec<_>>(),
+            obstacle_positions
+        );
     }
 
     #[test]
     fn test_puzzle_day6_part1() {
         let puzzle = Day6;
-        assert_eq!(puzzle.part1(&TESTCASE).unwrap(), 41);
+        assert_eq!(puzzle.part1(TESTCASE).unwrap(), 41.into());
     }
 
     #[test]
     fn test_puzzle_day6_part2() {
         let puzzle = Day6;
-        assert_eq!(puzzle.part2(&TESTCASE).unwrap(), 6);
+        assert_eq!(puzzle.part2(TESTCASE).unwrap(), 6.into());
     }
 }
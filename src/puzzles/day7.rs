@@ -1,4 +1,5 @@
-use super::Puzzle;
+use super::{parse_input, Answer, Solution};
+use crate::Result;
 
 pub struct Day7;
 
@@ -27,12 +28,6 @@ mod parser {
 }
 
 impl Day7 {
-    fn parse(&self, input: &str) -> Result<Input, String> {
-        let (_, input) = parser::parse(input).map_err(|err| format!("{:#?}", err))?;
-
-        Ok(input)
-    }
-
     fn concatenation(&self, a: i64, b: i64) -> i64 {
         // 计算 b 的位数
         let mut b_digits = 0;
@@ -47,12 +42,16 @@ impl Day7 {
     }
 }
 
-impl Puzzle for Day7 {
-    type Output = Result<i64, String>;
+impl Solution for Day7 {
+    const DAY: i32 = 7;
 
-    fn part1(&self, input: &str) -> Self::Output {
-        let equations = self.parse(&input).unwrap();
+    type Input<'a> = Input;
+
+    fn parse(&self, input: &str) -> Result<Input> {
+        parse_input(input, parser::parse)
+    }
 
+    fn solve1(&self, equations: &Self::Input<'_>) -> Result<Answer> {
         Ok(equations
             .iter()
             .filter(|(test_value, numbers)| {
@@ -60,17 +59,16 @@ impl Puzzle for Day7 {
                     .iter()
                     .skip(1)
                     .fold(vec![numbers[0]], |acc, e| {
-                        acc.iter().map(|n| vec![n + e, n * e]).flatten().collect()
+                        acc.iter().flat_map(|n| vec![n + e, n * e]).collect()
                     })
                     .contains(test_value)
             })
             .map(|(test_value, _)| *test_value)
-            .sum())
+            .sum::<i64>()
+            .into())
     }
 
-    fn part2(&self, input: &str) -> Self::Output {
-        let equations = self.parse(&input).unwrap();
-
+    fn solve2(&self, equations: &Self::Input<'_>) -> Result<Answer> {
         Ok(equations
             .iter()
             .filter(|(test_value, numbers)| {
@@ -79,22 +77,14 @@ impl Puzzle for Day7 {
                     .skip(1)
                     .fold(vec![numbers[0]], |acc, e| {
                         acc.iter()
-                            .map(|n| vec![n + e, n * e, self.concatenation(*n, *e)])
-                            .flatten()
+                            .flat_map(|n| vec![n + e, n * e, self.concatenation(*n, *e)])
                             .collect()
                     })
                     .contains(test_value)
             })
             .map(|(test_value, _)| *test_value)
-            .sum())
-    }
-
-    fn solve(&self, input: &str) {
-        let ans1 = self.part1(&input);
-        println!("Answer of Day 7 Part 1:  {:#?}", ans1.unwrap());
-
-        let ans2 = self.part2(&input);
-        println!("Answer of Day 7 Part 2:  {:#?}", ans2.unwrap());
+            .sum::<i64>()
+            .into())
     }
 }
 
@@ -103,8 +93,9 @@ mod tests {
     use std::vec;
 
     use super::*;
+    use crate::puzzles::Puzzle;
 
-    const TESTCASE: &'static str = r"190: 10 19
+    const TESTCASE: &str = r"190: 10 19
 3267: 81 40 27
 83: 17 5
 156: 15 6
@@ -130,7 +121,7 @@ mod tests {
             (292, vec![11, 6, 16, 20]),
         ];
 
-        let input = puzzle.parse(&TESTCASE).unwrap();
+        let input = puzzle.parse(TESTCASE).unwrap();
 
         assert_eq!(input, calibration_equations);
     }
@@ -138,12 +129,12 @@ mod tests {
     #[test]
     fn test_puzzle_day7_part1() {
         let puzzle = Day7;
-        assert_eq!(puzzle.part1(&TESTCASE).unwrap(), 3749);
+        assert_eq!(puzzle.part1(TESTCASE).unwrap(), 3749.into());
     }
 
     #[test]
     fn test_puzzle_day7_part2() {
         let puzzle = Day7;
-        assert_eq!(puzzle.part2(&TESTCASE).unwrap(), 11387);
+        assert_eq!(puzzle.part2(TESTCASE).unwrap(), 11387.into());
     }
 }
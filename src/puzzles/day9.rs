@@ -1,11 +1,12 @@
-use super::Puzzle;
+use super::{parse_input, Answer, Solution};
+use crate::Result;
 
 pub struct Day9;
 
 type Input = Vec<Disk>;
 
-#[derive(PartialEq, Eq, Debug)]
-enum Disk {
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(crate) enum Disk {
     Files(usize, usize),
     FreeSpace(usize),
 }
@@ -16,50 +17,40 @@ mod parser {
     use super::{Disk, Input};
 
     pub fn parse(input: &str) -> IResult<&str, Input> {
-        let (_, input) = nom::character::complete::digit1(input)?;
-
-        nom::combinator::map_res(
-            nom::sequence::terminated(
-                nom::multi::many1(nom::character::complete::anychar),
-                nom::character::complete::multispace0,
-            ),
+        nom::combinator::map(
+            nom::multi::many1(nom::character::complete::satisfy(|ch| ch.is_ascii_digit())),
             |nums| {
-                Ok::<Input, &str>(
-                    nums.iter()
-                        .enumerate()
-                        .map(|(idx, &c)| match idx % 2 {
-                            0 => Disk::Files(c.to_digit(10).unwrap() as usize, idx / 2),
-                            1 => Disk::FreeSpace(c.to_digit(10).unwrap() as usize),
-                            _ => unreachable!(),
-                        })
-                        .collect(),
-                )
+                nums.iter()
+                    .enumerate()
+                    .map(|(idx, &c)| {
+                        let size = c as usize - '0' as usize;
+                        match idx % 2 {
+                            0 => Disk::Files(size, idx / 2),
+                            _ => Disk::FreeSpace(size),
+                        }
+                    })
+                    .collect()
             },
         )(input)
     }
 }
 
-impl Day9 {
-    fn parse(&self, input: &str) -> Result<Input, String> {
-        let (_, input) = parser::parse(input).map_err(|err| format!("{:#?}", err))?;
-
-        Ok(input)
-    }
-}
+impl Solution for Day9 {
+    const DAY: i32 = 9;
 
-impl Puzzle for Day9 {
-    type Output = Result<i64, String>;
+    type Input<'a> = Input;
 
-    fn part1(&self, input: &str) -> Self::Output {
-        let input = self.parse(input)?;
+    fn parse(&self, input: &str) -> Result<Input> {
+        parse_input(input, parser::parse)
+    }
 
+    fn solve1(&self, input: &Self::Input<'_>) -> Result<Answer> {
         let mut disk_blocks: Vec<i64> = input
             .iter()
-            .map(|disk| match disk {
+            .flat_map(|disk| match disk {
                 Disk::Files(size, id) => vec![*id as i64; *size],
                 Disk::FreeSpace(size) => vec![-1; *size],
             })
-            .flatten()
             .collect();
 
         let mut left = 0;
@@ -86,11 +77,12 @@ impl Puzzle for Day9 {
             .filter(|&id| *id != -1)
             .enumerate()
             .map(|(id, size)| (id as i64) * size)
-            .sum())
+            .sum::<i64>()
+            .into())
     }
 
-    fn part2(&self, input: &str) -> Self::Output {
-        let mut disk = self.parse(input)?;
+    fn solve2(&self, input: &Self::Input<'_>) -> Result<Answer> {
+        let mut disk = input.clone();
 
         let mut right = disk.len() - 1;
 
@@ -134,22 +126,14 @@ impl Puzzle for Day9 {
 
         Ok(disk
             .iter()
-            .map(|disk| match disk {
+            .flat_map(|disk| match disk {
                 Disk::Files(size, id) => vec![*id as i64; *size],
                 Disk::FreeSpace(size) => vec![0; *size],
             })
-            .flatten()
             .enumerate()
             .map(|(id, size)| (id as i64) * size)
-            .sum())
-    }
-
-    fn solve(&self, input: &str) {
-        let ans1 = self.part1(&input);
-        println!("Answer of Day 9 Part 1: {:#?}", ans1.unwrap());
-
-        let ans2 = self.part2(&input);
-        println!("Answer of Day 9 Part 2: {:#?}", ans2.unwrap());
+            .sum::<i64>()
+            .into())
     }
 }
 
@@ -157,8 +141,9 @@ impl Puzzle for Day9 {
 mod tests {
 
     use super::*;
+    use crate::puzzles::Puzzle;
 
-    const TESTCASE: &'static str = r"2333133121414131402";
+    const TESTCASE: &str = r"2333133121414131402";
 
     #[test]
     fn test_puzzle_day9_parse() {
@@ -185,18 +170,18 @@ mod tests {
             Disk::Files(2, 9),
         ];
 
-        assert_eq!(puzzle.parse(&TESTCASE).unwrap(), disk_map);
+        assert_eq!(puzzle.parse(TESTCASE).unwrap(), disk_map);
     }
 
     #[test]
     fn test_puzzle_day9_part1() {
         let puzzle = Day9;
-        assert_eq!(puzzle.part1(&TESTCASE).unwrap(), 1928);
+        assert_eq!(puzzle.part1(TESTCASE).unwrap(), 1928.into());
     }
 
     #[test]
     fn test_puzzle_day9_part2() {
         let puzzle = Day9;
-        assert_eq!(puzzle.part2(&TESTCASE).unwrap(), 2858);
+        assert_eq!(puzzle.part2(TESTCASE).unwrap(), 2858.into());
     }
 }
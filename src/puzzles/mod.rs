@@ -1,26 +1,269 @@
-pub mod day1;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day8;
-pub mod day9;
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day13;
-pub mod day14;
-pub mod day15;
-pub mod day16;
-pub mod day17;
+use std::fmt;
+
+use nom::IResult;
+
+use crate::{error::ParseError, Error, Result};
+
+/// Declares the day modules and the registry of their puzzles in one place.
+macro_rules! puzzles {
+    ($($module:ident::$puzzle:ident),* $(,)?) => {
+        $(pub mod $module;)*
+
+        static PUZZLES: &[&(dyn Puzzle + Sync)] = &[$(&$module::$puzzle),*];
+    };
+}
+
+puzzles! {
+    day1::Day1,
+    day2::Day2,
+    day3::Day3,
+    day4::Day4,
+    day5::Day5,
+    day6::Day6,
+    day7::Day7,
+    day8::Day8,
+    day9::Day9,
+    day10::Day10,
+    day11::Day11,
+    day12::Day12,
+    day13::Day13,
+    day14::Day14,
+    day15::Day15,
+    day16::Day16,
+    day17::Day17,
+}
+
+/// Looks up the puzzle of `day` in the registry.
+pub fn get(day: i32) -> Option<&'static dyn Puzzle> {
+    PUZZLES
+        .iter()
+        .find(|puzzle| puzzle.day() == day)
+        .map(|puzzle| *puzzle as &dyn Puzzle)
+}
+
+/// Days with a registered puzzle, in ascending order.
+pub fn days() -> impl Iterator<Item = i32> {
+    PUZZLES.iter().map(|puzzle| puzzle.day())
+}
+
+/// Runs a nom `parser` over `input`, rejecting anything but whitespace left after it.
+fn parse_input<'a, T>(
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, T>,
+) -> Result<T> {
+    let (remaining, parsed) = parser(input).map_err(|err| ParseError::from_nom(input, err))?;
+
+    let trailing = remaining.trim_start();
+    if !trailing.is_empty() {
+        return Err(ParseError::at(
+            input,
+            input.len() - trailing.len(),
+            "unexpected trailing input",
+        )
+        .into());
+    }
+
+    Ok(parsed)
+}
+
+/// Puzzle of one day, solved from its input parsed once for both parts.
+///
+/// Every solution is a [`Puzzle`], which is how the registry and the commands see it.
+pub(crate) trait Solution {
+    const DAY: i32;
+
+    type Input<'a>;
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>>;
+
+    fn solve1(&self, input: &Self::Input<'_>) -> Result<Answer>;
+    fn solve2(&self, input: &Self::Input<'_>) -> Result<Answer>;
+}
+
+/// Parsed input of a puzzle, ready to solve either part.
+pub trait Prepared {
+    fn part(&self, part: u8) -> Result<Answer>;
+}
+
+struct Parsed<'a, T: Solution> {
+    solution: &'a T,
+    input: T::Input<'a>,
+}
+
+impl<T: Solution> Prepared for Parsed<'_, T> {
+    fn part(&self, part: u8) -> Result<Answer> {
+        match part {
+            1 => self.solution.solve1(&self.input),
+            2 => self.solution.solve2(&self.input),
+            part => Err(Error::UnsupportedPart(part)),
+        }
+    }
+}
 
 pub trait Puzzle {
-    type Output;
+    fn day(&self) -> i32;
+
+    /// Parses `input` once, giving back what both parts are solved from.
+    fn prepare<'a>(&'a self, input: &'a str) -> Result<Box<dyn Prepared + 'a>>;
+
+    fn part(&self, part: u8, input: &str) -> Result<Answer> {
+        self.prepare(input)?.part(part)
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        self.part(1, input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        self.part(2, input)
+    }
+
+    /// Solves both parts, reporting a failed part on stderr without skipping the other one.
+    fn solve(&self, input: &str) -> Result<()> {
+        let mut failures = 0;
+
+        for part in 1..=2 {
+            if let Err(err) = self.solve_part(part, input) {
+                eprintln!("Error of Day {} Part {}: {}", self.day(), part, err);
+                failures += 1;
+            }
+        }
+
+        match failures {
+            0 => Ok(()),
+            failures => Err(Error::Failed(failures)),
+        }
+    }
+
+    fn solve_part(&self, part: u8, input: &str) -> Result<()> {
+        let answer = self.part(part, input)?;
+        println!("Answer of Day {} Part {}: {}", self.day(), part, answer);
+
+        Ok(())
+    }
+}
+
+impl<T: Solution> Puzzle for T {
+    fn day(&self) -> i32 {
+        T::DAY
+    }
+
+    fn prepare<'a>(&'a self, input: &'a str) -> Result<Box<dyn Prepared + 'a>> {
+        Ok(Box::new(Parsed {
+            solution: self,
+            input: self.parse(input)?,
+        }))
+    }
+}
+
+/// Result of a puzzle part, shared by every day.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Answer {
+    Integer(i64),
+    BigInteger(u128),
+    Text(String),
+    Unsolved,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{}", value),
+            Answer::BigInteger(value) => write!(f, "{}", value),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Self {
+        Answer::Integer(value as i64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Integer(value)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        i64::try_from(value).map_or(Answer::BigInteger(value as u128), Answer::Integer)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::from(value as u64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_puzzles_registry() {
+        assert_eq!(days().collect::<Vec<_>>(), (1..=17).collect::<Vec<_>>());
+        assert!(get(1).is_some());
+        assert!(get(0).is_none());
+        assert!(get(26).is_none());
+
+        assert_eq!(get(1).unwrap().part(1, "3   4\n4   3\n").unwrap(), 0.into());
+        assert!(matches!(
+            get(1).unwrap().part(7, "3   4\n4   3\n"),
+            Err(Error::UnsupportedPart(7))
+        ));
+    }
+
+    #[test]
+    fn test_puzzles_solve_failure() {
+        let puzzle = get(17).unwrap();
+        let input = "Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,4,3,99";
+
+        assert!(matches!(puzzle.solve_part(1, input), Err(Error::Parse(_))));
+        assert!(matches!(puzzle.solve(input), Err(Error::Failed(2))));
+        assert!(get(1).unwrap().solve("3   4\n4   3\n").is_ok());
+    }
+
+    #[test]
+    fn test_puzzles_answer() {
+        assert_eq!(Answer::from(42_usize), Answer::Integer(42));
+        assert_eq!(Answer::from(u64::MAX), Answer::BigInteger(u64::MAX as u128));
+        assert_eq!(Answer::from(String::from("4,6,3")).to_string(), "4,6,3");
+        assert_eq!(Answer::Unsolved.to_string(), "unsolved");
+    }
+
+    #[test]
+    fn test_puzzles_parse_input() {
+        let parser = |input| {
+            nom::multi::separated_list1(
+                nom::character::complete::newline,
+                nom::character::complete::i32,
+            )(input)
+        };
+
+        assert_eq!(parse_input("1\n2\n3\n", parser).unwrap(), vec![1, 2, 3]);
 
-    fn part1(&self, input: &str) -> Self::Output;
-    fn part2(&self, input: &str) -> Self::Output;
+        match parse_input("1\n2\nx\n", parser) {
+            Err(Error::Parse(err)) => {
+                assert_eq!((err.line, err.column), (3, 1));
+                assert_eq!(err.message, "unexpected trailing input");
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
 
-    fn solve(&self, input: &str);
+        match parse_input("x", parser) {
+            Err(Error::Parse(err)) => assert_eq!((err.line, err.column), (1, 1)),
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
 }
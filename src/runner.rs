@@ -0,0 +1,448 @@
+use std::{
+    fmt,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::{
+    puzzles::{Answer, Puzzle},
+    Result,
+};
+
+/// Inclusive range of days given as `5`, `1..10` or `1..=10`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DayRange {
+    pub first: i32,
+    pub last: i32,
+}
+
+impl DayRange {
+    pub fn contains(&self, day: i32) -> bool {
+        (self.first..=self.last).contains(&day)
+    }
+}
+
+impl FromStr for DayRange {
+    type Err = String;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let parse_day = |day: &str| {
+            day.trim()
+                .parse::<i32>()
+                .map_err(|_| format!("invalid day `{}` in `{}`", day, spec))
+        };
+
+        let (first, last) = if let Some((first, last)) = spec.split_once("..=") {
+            (parse_day(first)?, parse_day(last)?)
+        } else if let Some((first, last)) = spec.split_once("..") {
+            (parse_day(first)?, parse_day(last)? - 1)
+        } else {
+            let day = parse_day(spec)?;
+            (day, day)
+        };
+
+        if first > last {
+            return Err(format!("day range `{}` is empty", spec));
+        }
+
+        Ok(Self { first, last })
+    }
+}
+
+pub struct PartReport {
+    pub answer: Result<Answer>,
+    /// Time spent solving from the parsed input, zero when the input failed to parse.
+    pub duration: Duration,
+}
+
+pub struct DayReport {
+    pub day: i32,
+    pub parse: Result<Duration>,
+    pub parts: Vec<PartReport>,
+}
+
+impl DayReport {
+    pub fn total(&self) -> Duration {
+        self.parse.as_ref().copied().unwrap_or_default()
+            + self
+                .parts
+                .iter()
+                .map(|part| part.duration)
+                .sum::<Duration>()
+    }
+}
+
+/// Runs both parts of `puzzle` from one parse of `input`, timing the parse and each part.
+pub fn run_day(puzzle: &dyn Puzzle, input: &str) -> DayReport {
+    let start = Instant::now();
+    let prepared = puzzle.prepare(input);
+    let parse_time = start.elapsed();
+
+    let (parse, parts) = match prepared {
+        Ok(prepared) => {
+            let parts = (1..=2)
+                .map(|part| {
+                    let start = Instant::now();
+                    let answer = prepared.part(part);
+
+                    PartReport {
+                        answer,
+                        duration: start.elapsed(),
+                    }
+                })
+                .collect();
+
+            (Ok(parse_time), parts)
+        }
+        // Every part fails with the parse error, which is not `Clone`, so each one asks for it.
+        Err(err) => {
+            let parts = (1..=2)
+                .map(|part| PartReport {
+                    answer: puzzle.part(part, input),
+                    duration: Duration::ZERO,
+                })
+                .collect();
+
+            (Err(err), parts)
+        }
+    };
+
+    DayReport {
+        day: puzzle.day(),
+        parse,
+        parts,
+    }
+}
+
+/// Human readable duration with three significant digits, e.g. `1.23ms`.
+pub struct Elapsed(pub Duration);
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nanos = self.0.as_nanos() as f64;
+        let (value, unit) = if nanos >= 1e9 {
+            (nanos / 1e9, "s")
+        } else if nanos >= 1e6 {
+            (nanos / 1e6, "ms")
+        } else if nanos >= 1e3 {
+            (nanos / 1e3, "µs")
+        } else {
+            (nanos, "ns")
+        };
+
+        let precision = if unit == "ns" || value >= 100.0 {
+            0
+        } else if value >= 10.0 {
+            1
+        } else {
+            2
+        };
+
+        f.pad(&format!("{:.*}{}", precision, value, unit))
+    }
+}
+
+fn answer_cell(answer: &Result<Answer>) -> String {
+    match answer {
+        Ok(answer) => answer.to_string(),
+        Err(err) => format!(
+            "error: {}",
+            err.to_string().lines().next().unwrap_or_default()
+        ),
+    }
+}
+
+/// Renders `reports` as a table of answers and durations with a total row.
+pub fn render_table(reports: &[DayReport]) -> String {
+    let rows: Vec<[String; 6]> = reports
+        .iter()
+        .map(|report| {
+            let parse = match &report.parse {
+                Ok(duration) => Elapsed(*duration).to_string(),
+                Err(_) => String::from("error"),
+            };
+
+            [
+                report.day.to_string(),
+                answer_cell(&report.parts[0].answer),
+                answer_cell(&report.parts[1].answer),
+                parse,
+                Elapsed(report.parts[0].duration).to_string(),
+                Elapsed(report.parts[1].duration).to_string(),
+            ]
+        })
+        .collect();
+
+    let header = ["Day", "Part 1", "Part 2", "Parse", "Time 1", "Time 2"];
+    let mut widths = header.map(|title| title.chars().count());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let render_row = |cells: [&str; 6]| {
+        let line = cells
+            .iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(idx, (cell, width))| match idx {
+                1 | 2 => format!("{:<width$}", cell),
+                _ => format!("{:>width$}", cell),
+            })
+            .collect::<Vec<String>>()
+            .join(" | ");
+
+        line.trim_end().to_string()
+    };
+
+    let mut table = vec![
+        render_row(header),
+        widths.map(|width| "-".repeat(width)).join("-+-"),
+    ];
+    table.extend(
+        rows.iter()
+            .map(|row| render_row(row.each_ref().map(String::as_str))),
+    );
+
+    let total: Duration = reports.iter().map(DayReport::total).sum();
+    let total_width = widths.iter().sum::<usize>() + 3 * (widths.len() - 1);
+    table.push("-".repeat(total_width));
+    table.push(format!(
+        "{:<width$}{}",
+        "Total",
+        Elapsed(total),
+        width = total_width - Elapsed(total).to_string().chars().count()
+    ));
+
+    table.join("\n")
+}
+
+/// Output format of [`render`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, clap::ValueEnum)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Solved,
+    Unsolved,
+    Error,
+}
+
+/// One row of the machine-readable output, one per day and part.
+#[derive(Serialize, PartialEq, Eq, Debug)]
+pub struct Record {
+    pub day: i32,
+    pub part: u8,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub error: Option<String>,
+    pub parse_ns: Option<u64>,
+    pub solve_ns: u64,
+}
+
+#[derive(Serialize)]
+struct Summary {
+    results: Vec<Record>,
+    total_ns: u64,
+}
+
+impl Record {
+    const CSV_HEADER: &'static str = "day,part,status,answer,error,parse_ns,solve_ns";
+
+    fn to_csv(&self) -> String {
+        let field = |value: &Option<String>| value.as_deref().map(csv_escape).unwrap_or_default();
+        let parse_ns = self.parse_ns.map(|ns| ns.to_string()).unwrap_or_default();
+        let status = match self.status {
+            Status::Solved => "solved",
+            Status::Unsolved => "unsolved",
+            Status::Error => "error",
+        };
+
+        format!(
+            "{},{},{},{},{},{},{}",
+            self.day,
+            self.part,
+            status,
+            field(&self.answer),
+            field(&self.error),
+            parse_ns,
+            self.solve_ns
+        )
+    }
+}
+
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+fn first_line(err: &crate::Error) -> String {
+    err.to_string()
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Flattens `reports` into one [`Record`] per day and part.
+pub fn records(reports: &[DayReport]) -> Vec<Record> {
+    reports
+        .iter()
+        .flat_map(|report| {
+            let parse_ns = report.parse.as_ref().ok().map(|duration| nanos(*duration));
+
+            report.parts.iter().zip(1..).map(move |(part, idx)| {
+                let (status, answer, error) = match &part.answer {
+                    Ok(Answer::Unsolved) => (Status::Unsolved, None, None),
+                    Ok(answer) => (Status::Solved, Some(answer.to_string()), None),
+                    Err(err) => (Status::Error, None, Some(first_line(err))),
+                };
+
+                Record {
+                    day: report.day,
+                    part: idx,
+                    status,
+                    answer,
+                    error,
+                    parse_ns,
+                    solve_ns: nanos(part.duration),
+                }
+            })
+        })
+        .collect()
+}
+
+/// Renders `reports` in `format`, the JSON and CSV schemas are stable across runs.
+pub fn render(reports: &[DayReport], format: Format) -> String {
+    match format {
+        Format::Text => render_table(reports),
+        Format::Json => {
+            let summary = Summary {
+                results: records(reports),
+                total_ns: nanos(reports.iter().map(DayReport::total).sum()),
+            };
+            serde_json::to_string_pretty(&summary).expect("records always serialize")
+        }
+        Format::Csv => std::iter::once(Record::CSV_HEADER.to_string())
+            .chain(records(reports).iter().map(Record::to_csv))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzles;
+
+    #[test]
+    fn test_day_range_from_str() {
+        assert_eq!("5".parse(), Ok(DayRange { first: 5, last: 5 }));
+        assert_eq!("1..=10".parse(), Ok(DayRange { first: 1, last: 10 }));
+        assert_eq!("1..10".parse(), Ok(DayRange { first: 1, last: 9 }));
+        assert!("10..=1".parse::<DayRange>().is_err());
+        assert!("a..=3".parse::<DayRange>().is_err());
+
+        assert!(DayRange { first: 1, last: 10 }.contains(10));
+        assert!(!DayRange { first: 1, last: 10 }.contains(11));
+    }
+
+    #[test]
+    fn test_elapsed_display() {
+        assert_eq!(Elapsed(Duration::ZERO).to_string(), "0ns");
+        assert_eq!(Elapsed(Duration::from_nanos(512)).to_string(), "512ns");
+        assert_eq!(Elapsed(Duration::from_micros(1234)).to_string(), "1.23ms");
+        assert_eq!(Elapsed(Duration::from_millis(45600)).to_string(), "45.6s");
+    }
+
+    #[test]
+    fn test_run_day() {
+        let puzzle = puzzles::get(1).unwrap();
+        let report = run_day(puzzle, "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n");
+
+        assert_eq!(report.day, 1);
+        assert!(report.parse.is_ok());
+        assert_eq!(report.parts[0].answer.as_ref().unwrap(), &11.into());
+        assert_eq!(report.parts[1].answer.as_ref().unwrap(), &31.into());
+
+        let report = run_day(puzzle, "3   4\n4\n");
+        assert!(report.parse.is_err());
+        assert!(report.parts[0].answer.is_err());
+    }
+
+    #[test]
+    fn test_render_table() {
+        let puzzle = puzzles::get(1).unwrap();
+        let reports = [
+            run_day(puzzle, "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n"),
+            run_day(puzzle, "3   4\n4\n"),
+        ];
+
+        let table = render_table(&reports);
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines.len(), 6);
+        assert!(lines[0].starts_with("Day | Part 1"));
+        assert!(lines[2].starts_with("  1 | 11 "));
+        assert!(lines[3].contains("error: failed to parse input at line 2, column 2"));
+        assert!(lines[5].starts_with("Total"));
+        assert_eq!(lines[4].len(), lines[5].chars().count());
+    }
+
+    #[test]
+    fn test_render_json_and_csv() {
+        let puzzle = puzzles::get(1).unwrap();
+        let reports = [
+            run_day(puzzle, "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n"),
+            run_day(puzzle, "3   4\n4\n"),
+        ];
+
+        let records = records(&reports);
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[1].part, 2);
+        assert_eq!(records[1].status, Status::Solved);
+        assert_eq!(records[1].answer.as_deref(), Some("31"));
+        assert_eq!(records[2].status, Status::Error);
+        assert_eq!(records[2].parse_ns, None);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render(&reports, Format::Json)).unwrap();
+        assert_eq!(json["results"][0]["answer"], "11");
+        assert_eq!(json["results"][0]["status"], "solved");
+        assert!(json["results"][2]["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("failed to parse"));
+        assert!(json["total_ns"].is_u64());
+
+        let csv = render(&reports, Format::Csv);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], "day,part,status,answer,error,parse_ns,solve_ns");
+        assert!(lines[1].starts_with("1,1,solved,11,,"));
+        assert!(lines[3].starts_with("1,1,error,,\"failed to parse input at line 2, column 2"));
+    }
+
+    #[test]
+    fn test_csv_escape() {
+        assert_eq!(csv_escape("plain"), "plain");
+        assert_eq!(csv_escape("a,b"), "\"a,b\"");
+        assert_eq!(csv_escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}